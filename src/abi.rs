@@ -0,0 +1,260 @@
+//! Minimal ABI encoding and decoding for contract calls
+//!
+//! This only covers the types needed by the contract helpers of this crate, like
+//! [Multicall](crate::multicall::Multicall). Encode arguments as a list of [Token]s and read
+//! return values word by word with a [Decoder].
+
+use crate::types::{Bytes, H160, H256, U256};
+use thiserror::Error;
+
+/// A single ABI encodable value
+#[derive(Clone, Debug, PartialEq)]
+pub enum Token {
+    Address(H160),
+    Uint(U256),
+    Bool(bool),
+    /// A `bytesN` value with up to 32 bytes
+    FixedBytes(Vec<u8>),
+    Bytes(Vec<u8>),
+    String(String),
+    /// A dynamically sized array `T[]`
+    Array(Vec<Token>),
+    Tuple(Vec<Token>),
+}
+
+impl Token {
+    fn is_dynamic(&self) -> bool {
+        match self {
+            Token::Bytes(_) | Token::String(_) | Token::Array(_) => true,
+            Token::Tuple(tokens) => tokens.iter().any(Token::is_dynamic),
+            _ => false,
+        }
+    }
+}
+
+/// Encodes the tokens like the arguments of a function
+pub fn encode(tokens: &[Token]) -> Vec<u8> {
+    let head_size: usize = tokens
+        .iter()
+        .map(|token| {
+            if token.is_dynamic() {
+                32
+            } else {
+                encode_token(token).len()
+            }
+        })
+        .sum();
+
+    let mut head = Vec::with_capacity(head_size);
+    let mut tail = Vec::new();
+    for token in tokens {
+        if token.is_dynamic() {
+            head.extend_from_slice(&uint_word(U256::from(head_size + tail.len())));
+            tail.extend(encode_token(token));
+        } else {
+            head.extend(encode_token(token));
+        }
+    }
+    head.extend(tail);
+    head
+}
+
+/// Encodes a function call from the 4 byte selector and the arguments
+pub fn encode_call(selector: [u8; 4], tokens: &[Token]) -> Bytes {
+    let mut call = selector.to_vec();
+    call.extend(encode(tokens));
+    Bytes(call)
+}
+
+fn encode_token(token: &Token) -> Vec<u8> {
+    match token {
+        Token::Address(address) => H256::from(*address).as_bytes().to_vec(),
+        Token::Uint(value) => uint_word(*value).to_vec(),
+        Token::Bool(value) => uint_word(U256::from(*value as u8)).to_vec(),
+        Token::FixedBytes(bytes) => padded(bytes),
+        Token::Bytes(bytes) => {
+            let mut encoded = uint_word(U256::from(bytes.len())).to_vec();
+            encoded.extend(padded(bytes));
+            encoded
+        }
+        Token::String(string) => encode_token(&Token::Bytes(string.as_bytes().to_vec())),
+        Token::Array(tokens) => {
+            let mut encoded = uint_word(U256::from(tokens.len())).to_vec();
+            encoded.extend(encode(tokens));
+            encoded
+        }
+        Token::Tuple(tokens) => encode(tokens),
+    }
+}
+
+fn uint_word(value: U256) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    value.to_big_endian(&mut word);
+    word
+}
+
+fn padded(bytes: &[u8]) -> Vec<u8> {
+    let mut padded = bytes.to_vec();
+    padded.resize(bytes.len() + (32 - bytes.len() % 32) % 32, 0);
+    padded
+}
+
+/// Reads ABI encoded values word by word
+///
+/// Static values are read at their word index. Dynamic values are stored at an offset, which
+/// is read at their word index and resolved relative to the start of this decoder.
+#[derive(Clone, Copy, Debug)]
+pub struct Decoder<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Decoder { data }
+    }
+
+    /// Returns the raw word at `index`
+    pub fn word(&self, index: usize) -> Result<&'a [u8], AbiError> {
+        let start = index.checked_mul(32).ok_or(AbiError::OutOfBounds)?;
+        self.slice(start, 32)
+    }
+
+    pub fn uint(&self, index: usize) -> Result<U256, AbiError> {
+        Ok(U256::from_big_endian(self.word(index)?))
+    }
+
+    /// Returns the `uint8` at `index`, failing for values above `u8::MAX`
+    pub fn uint8(&self, index: usize) -> Result<u8, AbiError> {
+        let value = self.uint(index)?;
+        if value > U256::from(u8::MAX) {
+            return Err(AbiError::Overflow(value));
+        }
+        Ok(value.low_u32() as u8)
+    }
+
+    pub fn address(&self, index: usize) -> Result<H160, AbiError> {
+        Ok(H160::from_slice(&self.word(index)?[12..]))
+    }
+
+    pub fn bool(&self, index: usize) -> Result<bool, AbiError> {
+        Ok(!self.uint(index)?.is_zero())
+    }
+
+    pub fn fixed_bytes(&self, index: usize) -> Result<H256, AbiError> {
+        Ok(H256::from_slice(self.word(index)?))
+    }
+
+    pub fn bytes(&self, index: usize) -> Result<Vec<u8>, AbiError> {
+        let tail = self.tail(index)?;
+        let len = tail.usize(0)?;
+        Ok(tail.slice(32, len)?.to_vec())
+    }
+
+    pub fn string(&self, index: usize) -> Result<String, AbiError> {
+        Ok(String::from_utf8(self.bytes(index)?)?)
+    }
+
+    /// Returns the length of the array referenced at `index` and a decoder for its elements
+    pub fn array(&self, index: usize) -> Result<(usize, Decoder<'a>), AbiError> {
+        let tail = self.tail(index)?;
+        let len = tail.usize(0)?;
+        let elements = tail.data.get(32..).ok_or(AbiError::OutOfBounds)?;
+        Ok((len, Decoder::new(elements)))
+    }
+
+    /// Returns a decoder for the dynamic tuple or value referenced at `index`
+    pub fn tail(&self, index: usize) -> Result<Decoder<'a>, AbiError> {
+        let offset = self.usize(index)?;
+        Ok(Decoder::new(self.slice(offset, self.data.len() - offset)?))
+    }
+
+    fn usize(&self, index: usize) -> Result<usize, AbiError> {
+        let value = self.uint(index)?;
+        if value > U256::from(self.data.len()) {
+            return Err(AbiError::OutOfBounds);
+        }
+        Ok(value.as_usize())
+    }
+
+    fn slice(&self, start: usize, len: usize) -> Result<&'a [u8], AbiError> {
+        start
+            .checked_add(len)
+            .and_then(|end| self.data.get(start..end))
+            .ok_or(AbiError::OutOfBounds)
+    }
+}
+
+/// An error type collecting what can go wrong when decoding ABI data
+#[derive(Debug, Error)]
+pub enum AbiError {
+    #[error("Abi Error: Data is too short")]
+    OutOfBounds,
+    #[error("Abi Error: String is not valid utf8: {0}")]
+    Utf8(#[from] std::string::FromUtf8Error),
+    #[error("Abi Error: Value {0} is out of range of its type")]
+    Overflow(U256),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_abi_encode() {
+        // f(uint256,bytes,(address,bool)) with 1, 0x1234 and (0x01, true)
+        let encoded = encode(&[
+            Token::Uint(U256::one()),
+            Token::Bytes(vec![0x12, 0x34]),
+            Token::Tuple(vec![
+                Token::Address(H160::from_low_u64_be(1)),
+                Token::Bool(true),
+            ]),
+        ]);
+        let expected = Bytes::from_str(concat!(
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "0000000000000000000000000000000000000000000000000000000000000080",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "1234000000000000000000000000000000000000000000000000000000000000",
+        ))
+        .unwrap();
+        assert_eq!(encoded, expected.0);
+    }
+
+    #[test]
+    fn test_abi_decode() {
+        // (string, uint256[]) with "ethane" and [7, 8]
+        let data = Bytes::from_str(concat!(
+            "0000000000000000000000000000000000000000000000000000000000000040",
+            "0000000000000000000000000000000000000000000000000000000000000080",
+            "0000000000000000000000000000000000000000000000000000000000000006",
+            "657468616e650000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "0000000000000000000000000000000000000000000000000000000000000007",
+            "0000000000000000000000000000000000000000000000000000000000000008",
+        ))
+        .unwrap();
+        let decoder = Decoder::new(&data.0);
+        assert_eq!(decoder.string(0).unwrap(), "ethane");
+        let (len, array) = decoder.array(1).unwrap();
+        assert_eq!(len, 2);
+        assert_eq!(array.uint(1).unwrap(), U256::from(8));
+        assert_eq!(
+            encode(&[
+                Token::String(String::from("ethane")),
+                Token::Array(vec![Token::Uint(7.into()), Token::Uint(8.into())]),
+            ]),
+            data.0
+        );
+        assert!(matches!(decoder.word(7), Err(AbiError::OutOfBounds)));
+        assert!(matches!(decoder.bytes(2), Err(AbiError::OutOfBounds)));
+        assert_eq!(array.uint8(0).unwrap(), 7);
+        let large = encode(&[Token::Uint(U256::from(256))]);
+        assert!(matches!(
+            Decoder::new(&large).uint8(0),
+            Err(AbiError::Overflow(value)) if value == U256::from(256)
+        ));
+    }
+}
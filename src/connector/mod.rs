@@ -12,11 +12,14 @@ use crate::transport::uds::{Uds, UdsError};
 use log::{debug, error, info, trace};
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::fmt::Debug;
 use std::marker::PhantomData;
 use thiserror::Error;
 
+/// Number of request ids of a connector, which is also the maximum size of a batch
+pub const MAX_BATCH_SIZE: usize = 1000;
+
 pub mod subscription;
 use subscription::Subscription;
 
@@ -35,7 +38,7 @@ impl Connector<Http> {
         info!("Creating connector over http");
         Ok(Connector {
             connection: Http::new(String::from(domain), credentials),
-            id_pool: (0..1000).collect(),
+            id_pool: (0..MAX_BATCH_SIZE).collect(),
         })
     }
 }
@@ -49,7 +52,7 @@ impl Connector<WebSocket> {
         info!("Creating connector over websocket");
         Ok(Connector {
             connection: WebSocket::new(String::from(domain), credentials)?,
-            id_pool: (0..1000).collect(),
+            id_pool: (0..MAX_BATCH_SIZE).collect(),
         })
     }
 }
@@ -61,7 +64,7 @@ impl Connector<Uds> {
         info!("Creating connector over unix domain socket");
         Ok(Connector {
             connection: Uds::new(String::from(path))?,
-            id_pool: (0..1000).collect(),
+            id_pool: (0..MAX_BATCH_SIZE).collect(),
         })
     }
 }
@@ -103,9 +106,61 @@ impl<T: Request> Connector<T> {
         self.id_pool.push_back(command_id);
         deserialize_from_rpc(&response)
     }
+
+    /// Sends several requests with the same result type in a single JSON-RPC batch.
+    ///
+    /// Results are returned in the order of the requests. An error response for a single request
+    /// does not fail the whole batch. Batches with more than [MAX_BATCH_SIZE] requests fail with
+    /// [ConnectorError::NoTicketId].
+    // Keeps the error type of `call`, so that results of both can be handled alike
+    #[allow(clippy::result_large_err)]
+    pub fn call_batch<U: DeserializeOwned + Debug>(
+        &mut self,
+        mut rpcs: Vec<Rpc<U>>,
+    ) -> Result<Vec<Result<U, ConnectorError>>, ConnectorError> {
+        if rpcs.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut command_ids = Vec::with_capacity(rpcs.len());
+        for rpc in rpcs.iter_mut() {
+            match self.get_command_id() {
+                Ok(command_id) => {
+                    rpc.id = command_id;
+                    command_ids.push(command_id);
+                }
+                Err(err) => {
+                    self.id_pool.extend(command_ids);
+                    return Err(err);
+                }
+            }
+        }
+        debug!("Calling batch of {} rpc methods", rpcs.len());
+        let response = self.connection.request(serde_json::to_string(&rpcs)?);
+        self.id_pool.extend(command_ids.iter().copied());
+        deserialize_batch_from_rpc(&response?, &command_ids)
+    }
 }
 
 impl<T> Connector<T> {
+    /// Creates a connector for an arbitrary transport, used to mock nodes in tests
+    #[cfg(test)]
+    pub(crate) fn with_transport(connection: T) -> Self {
+        Connector {
+            connection,
+            id_pool: (0..MAX_BATCH_SIZE).collect(),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn transport(&self) -> &T {
+        &self.connection
+    }
+
+    #[cfg(test)]
+    pub(crate) fn transport_mut(&mut self) -> &mut T {
+        &mut self.connection
+    }
+
     fn get_command_id(&mut self) -> Result<usize, ConnectorError> {
         match self.id_pool.pop_front() {
             Some(inner) => {
@@ -131,6 +186,38 @@ fn deserialize_from_rpc<U: DeserializeOwned + Debug>(response: &str) -> Result<U
     }
 }
 
+#[allow(clippy::result_large_err)]
+fn deserialize_batch_from_rpc<U: DeserializeOwned + Debug>(
+    response: &str,
+    command_ids: &[usize],
+) -> Result<Vec<Result<U, ConnectorError>>, ConnectorError> {
+    trace!("Deserializing batch response {}", response);
+    let responses = match serde_json::from_str::<Vec<BatchResponse<U>>>(response) {
+        Ok(inner) => inner,
+        // The node answers with a single error if it rejects the whole batch
+        Err(err) => {
+            deserialize_from_rpc::<serde_json::Value>(response)?;
+            return Err(ConnectorError::from(err));
+        }
+    };
+    // Errors without an id cannot be matched to a request, so they count as missing responses
+    let mut results: HashMap<usize, Result<U, ConnectorError>> = responses
+        .into_iter()
+        .filter_map(|response| match response.result_or_error {
+            RpcResult::Result(result) => Some((response.id?, Ok(result))),
+            RpcResult::Error(err) => Some((response.id?, Err(ConnectorError::from(err)))),
+        })
+        .collect();
+    Ok(command_ids
+        .iter()
+        .map(|id| {
+            results
+                .remove(id)
+                .unwrap_or(Err(ConnectorError::MissingBatchResponse(*id)))
+        })
+        .collect())
+}
+
 /// Used to deserialize errors returned from the ethereum node
 #[derive(Deserialize, Debug, Error)]
 #[error("{message}")]
@@ -139,6 +226,18 @@ pub struct JsonError {
     message: String,
 }
 
+impl JsonError {
+    /// The JSON-RPC error code
+    pub fn code(&self) -> i32 {
+        self.code
+    }
+
+    /// The error message of the node
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
 #[derive(Deserialize, Debug)]
 struct Response<T> {
     #[serde(rename = "jsonrpc")]
@@ -147,6 +246,13 @@ struct Response<T> {
     pub result_or_error: RpcResult<T>,
 }
 
+#[derive(Deserialize, Debug)]
+struct BatchResponse<T> {
+    id: Option<usize>,
+    #[serde(flatten)]
+    result_or_error: RpcResult<T>,
+}
+
 #[derive(Deserialize, Debug)]
 enum RpcResult<T> {
     #[serde(rename = "result")]
@@ -172,4 +278,25 @@ pub enum ConnectorError {
     JsonRpc(#[from] JsonError),
     #[error("Connector De-/Serialization Error: {0}")]
     Serde(#[from] serde_json::Error),
+    #[error("Connector Error: Batch response is missing request with id {0}")]
+    MissingBatchResponse(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connector_batch_response_without_id() {
+        let response = r#"[
+            {"jsonrpc": "2.0", "id": 1, "result": "0x2a"},
+            {"jsonrpc": "2.0", "id": null, "error": {"code": -32600, "message": "invalid request"}}
+        ]"#;
+        let results = deserialize_batch_from_rpc::<String>(response, &[1, 2]).unwrap();
+        assert_eq!(results[0].as_ref().unwrap(), "0x2a");
+        assert!(matches!(
+            results[1],
+            Err(ConnectorError::MissingBatchResponse(2))
+        ));
+    }
 }
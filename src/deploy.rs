@@ -0,0 +1,110 @@
+//! Computation of contract addresses before deployment
+//!
+//! Contracts created by a transaction without `to` or by the `CREATE` opcode get an address
+//! derived from the sender and its nonce. The `CREATE2` opcode of
+//! [EIP-1014](https://eips.ethereum.org/EIPS/eip-1014) derives it from the deployer, a salt and
+//! the hash of the init code instead, which allows counterfactual deployments.
+
+use crate::rpc;
+use crate::transport::Request;
+use crate::types::{BlockParameter, H160, H256, U256};
+use crate::utils::keccak256;
+use crate::{Connector, ConnectorError};
+use rlp::RlpStream;
+use thiserror::Error;
+
+/// Computes the address of a contract created by `sender` with `nonce`
+pub fn create_address(sender: H160, nonce: U256) -> H160 {
+    let mut stream = RlpStream::new_list(2);
+    stream.append(&sender).append(&nonce);
+    H160::from_slice(&keccak256(&stream.out())[12..])
+}
+
+/// Computes the address of a contract created by `deployer` with `CREATE2`
+pub fn create2_address(deployer: H160, salt: H256, init_code_hash: H256) -> H160 {
+    let mut input = vec![0xff];
+    input.extend_from_slice(deployer.as_bytes());
+    input.extend_from_slice(salt.as_bytes());
+    input.extend_from_slice(init_code_hash.as_bytes());
+    H160::from_slice(&keccak256(&input)[12..])
+}
+
+/// Predicts the address of the next contract deployed by `sender`
+///
+/// The nonce is taken from [eth_get_transaction_count](crate::rpc::eth_get_transaction_count) at
+/// `block_param`, which defaults to `BlockParameter::Pending`, so that transactions of the sender
+/// which are still in the transaction pool are taken into account.
+pub fn predict_create_address<T: Request>(
+    connector: &mut Connector<T>,
+    sender: H160,
+    block_param: Option<BlockParameter>,
+) -> Result<H160, DeployError> {
+    let block_param = block_param.unwrap_or(BlockParameter::Pending);
+    let nonce = connector.call(rpc::eth_get_transaction_count(
+        sender,
+        Some(block_param.into()),
+    ))?;
+    Ok(create_address(sender, nonce))
+}
+
+/// An error type collecting what can go wrong when predicting contract addresses
+#[derive(Debug, Error)]
+pub enum DeployError {
+    #[error("Deploy Error: {0}")]
+    Connector(Box<ConnectorError>),
+}
+
+impl From<ConnectorError> for DeployError {
+    fn from(err: ConnectorError) -> Self {
+        DeployError::Connector(Box::new(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_deploy_create_address() {
+        let sender = H160::from_str("0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0").unwrap();
+        let expected = [
+            "0xcd234a471b72ba2f1ccf0a70fcaba648a5eecd8d",
+            "0x343c43a37d37dff08ae8c4a11544c718abb4fcf8",
+            "0xf778b86fa74e846c4f0a1fbd1335fe81c00a0c91",
+            "0xfffd933a0bc612844eaf0c6fe3e5b8e9b6c1d19c",
+        ];
+        for (nonce, address) in expected.iter().enumerate() {
+            assert_eq!(
+                create_address(sender, U256::from(nonce)),
+                H160::from_str(address).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_deploy_create2_address() {
+        // Examples of EIP-1014
+        let init_code_hash = H256::from(keccak256(&[0x00]));
+        assert_eq!(
+            create2_address(H160::zero(), H256::zero(), init_code_hash),
+            H160::from_str("0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38").unwrap()
+        );
+        assert_eq!(
+            create2_address(
+                H160::from_str("0xdeadbeef00000000000000000000000000000000").unwrap(),
+                H256::zero(),
+                init_code_hash
+            ),
+            H160::from_str("0xB928f69Bb1D91Cd65274e3c79d8986362984fDA3").unwrap()
+        );
+        assert_eq!(
+            create2_address(
+                H160::from_str("0x00000000000000000000000000000000deadbeef").unwrap(),
+                H256::from_low_u64_be(0xcafebabe),
+                H256::from(keccak256(&hex::decode("deadbeef").unwrap()))
+            ),
+            H160::from_str("0x60f3f640a8508fC6a86d45DF051962668E1e8AC7").unwrap()
+        );
+    }
+}
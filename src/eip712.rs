@@ -0,0 +1,383 @@
+//! Hashing and signing of structured data as defined in [EIP-712](https://eips.ethereum.org/EIPS/eip-712)
+//!
+//! [TypedData] is hashed the same way wallets do for `eth_signTypedData_v4`, so
+//! [sign_typed_data] yields the same signature as
+//! [eth_sign_typed_data_v4](crate::rpc::eth_sign_typed_data_v4) for the same key.
+//!
+//! If `types` does not contain `EIP712Domain`, it is derived from the fields present in `domain`.
+
+use crate::signing::{sign_hash, SigningError};
+use crate::types::{PrivateKey, Signature, TypedData, TypedDataField, H160, H256, U256};
+use crate::utils::keccak256;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::str::FromStr;
+use thiserror::Error;
+
+const DOMAIN_TYPE: &str = "EIP712Domain";
+
+// Fields of the domain in the order defined by EIP-712
+const DOMAIN_FIELDS: [(&str, &str); 5] = [
+    ("name", "string"),
+    ("version", "string"),
+    ("chainId", "uint256"),
+    ("verifyingContract", "address"),
+    ("salt", "bytes32"),
+];
+
+/// Computes the hash which is signed, `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(message))`
+pub fn hash_typed_data(typed_data: &TypedData) -> Result<H256, Eip712Error> {
+    let mut input = vec![0x19, 0x01];
+    input.extend_from_slice(domain_separator(typed_data)?.as_bytes());
+    if typed_data.primary_type != DOMAIN_TYPE {
+        let message = hash_struct(typed_data, &typed_data.primary_type, &typed_data.message)?;
+        input.extend_from_slice(message.as_bytes());
+    }
+    Ok(H256::from(keccak256(&input)))
+}
+
+/// Computes the hash of the domain
+pub fn domain_separator(typed_data: &TypedData) -> Result<H256, Eip712Error> {
+    hash_struct(typed_data, DOMAIN_TYPE, &typed_data.domain)
+}
+
+/// Computes `hashStruct` of `value`, which must be an object of the struct type `type_name`
+pub fn hash_struct(
+    typed_data: &TypedData,
+    type_name: &str,
+    value: &Value,
+) -> Result<H256, Eip712Error> {
+    let fields = fields(typed_data, type_name)?;
+    let mut encoded = keccak256(encode_type(typed_data, type_name)?.as_bytes()).to_vec();
+    for field in fields.iter() {
+        let field_value = value.get(&field.name).unwrap_or(&Value::Null);
+        encoded.extend_from_slice(&encode_value(typed_data, &field.field_type, field_value)?);
+    }
+    Ok(H256::from(keccak256(&encoded)))
+}
+
+/// Encodes a struct type with all referenced struct types, like `Mail(Person from,...)Person(...)`
+pub fn encode_type(typed_data: &TypedData, type_name: &str) -> Result<String, Eip712Error> {
+    let mut referenced = BTreeSet::new();
+    collect_references(typed_data, type_name, &mut referenced)?;
+    referenced.remove(type_name);
+
+    let mut encoded = String::new();
+    for name in std::iter::once(type_name).chain(referenced.iter().map(String::as_str)) {
+        let members: Vec<String> = fields(typed_data, name)?
+            .iter()
+            .map(|field| format!("{} {}", field.field_type, field.name))
+            .collect();
+        encoded += &format!("{}({})", name, members.join(","));
+    }
+    Ok(encoded)
+}
+
+/// Signs typed data with a local key
+pub fn sign_typed_data(
+    typed_data: &TypedData,
+    private_key: &PrivateKey,
+) -> Result<Signature, Eip712Error> {
+    Ok(sign_hash(hash_typed_data(typed_data)?, private_key)?)
+}
+
+fn fields(typed_data: &TypedData, type_name: &str) -> Result<Vec<TypedDataField>, Eip712Error> {
+    if let Some(fields) = typed_data.types.get(type_name) {
+        return Ok(fields.clone());
+    }
+    if type_name != DOMAIN_TYPE {
+        return Err(Eip712Error::UnknownType(type_name.to_string()));
+    }
+    let fields = DOMAIN_FIELDS
+        .iter()
+        .filter(|(name, _)| typed_data.domain.get(name).is_some())
+        .map(|(name, field_type)| TypedDataField {
+            name: name.to_string(),
+            field_type: field_type.to_string(),
+        })
+        .collect();
+    Ok(fields)
+}
+
+fn collect_references(
+    typed_data: &TypedData,
+    type_name: &str,
+    referenced: &mut BTreeSet<String>,
+) -> Result<(), Eip712Error> {
+    if !referenced.insert(type_name.to_string()) {
+        return Ok(());
+    }
+    for field in fields(typed_data, type_name)? {
+        let base_type = field.field_type.split('[').next().unwrap_or_default();
+        if typed_data.types.contains_key(base_type) {
+            collect_references(typed_data, base_type, referenced)?;
+        }
+    }
+    Ok(())
+}
+
+fn encode_value(
+    typed_data: &TypedData,
+    field_type: &str,
+    value: &Value,
+) -> Result<[u8; 32], Eip712Error> {
+    let invalid = || Eip712Error::InvalidValue {
+        field_type: field_type.to_string(),
+        value: value.clone(),
+    };
+
+    // Arrays are hashed as the concatenation of their encoded elements
+    if let Some(element_type) = field_type.strip_suffix(']') {
+        let (element_type, length) = element_type.rsplit_once('[').ok_or_else(invalid)?;
+        let elements = value.as_array().ok_or_else(invalid)?;
+        if !length.is_empty() {
+            let length: usize = length
+                .parse()
+                .map_err(|_| Eip712Error::UnknownType(field_type.to_string()))?;
+            if elements.len() != length {
+                return Err(invalid());
+            }
+        }
+        let mut encoded = Vec::new();
+        for element in elements {
+            encoded.extend_from_slice(&encode_value(typed_data, element_type, element)?);
+        }
+        return Ok(keccak256(&encoded));
+    }
+    if typed_data.types.contains_key(field_type) {
+        return Ok(hash_struct(typed_data, field_type, value)?.0);
+    }
+
+    let word = match field_type {
+        "string" => keccak256(value.as_str().ok_or_else(invalid)?.as_bytes()),
+        "bytes" => keccak256(&parse_hex(value).ok_or_else(invalid)?),
+        "bool" => uint_word(U256::from(value.as_bool().ok_or_else(invalid)? as u8)),
+        "address" => {
+            let address = H160::from_str(value.as_str().ok_or_else(invalid)?);
+            H256::from(address.map_err(|_| invalid())?).0
+        }
+        _ if field_type.starts_with("uint") => uint_word(parse_uint(value).ok_or_else(invalid)?),
+        _ if field_type.starts_with("int") => uint_word(parse_int(value).ok_or_else(invalid)?),
+        _ if field_type.starts_with("bytes") => {
+            let size: usize = field_type["bytes".len()..]
+                .parse()
+                .ok()
+                .filter(|size| (1..=32).contains(size))
+                .ok_or_else(|| Eip712Error::UnknownType(field_type.to_string()))?;
+            let bytes = parse_hex(value).filter(|bytes| bytes.len() == size);
+            let bytes = bytes.ok_or_else(invalid)?;
+            let mut word = [0u8; 32];
+            word[..bytes.len()].copy_from_slice(&bytes);
+            word
+        }
+        _ => return Err(Eip712Error::UnknownType(field_type.to_string())),
+    };
+    Ok(word)
+}
+
+fn uint_word(value: U256) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    value.to_big_endian(&mut word);
+    word
+}
+
+fn parse_hex(value: &Value) -> Option<Vec<u8>> {
+    let value = value.as_str()?;
+    hex::decode(value.strip_prefix("0x").unwrap_or(value)).ok()
+}
+
+// Numbers are given as JSON numbers or as decimal or hex strings
+fn parse_uint(value: &Value) -> Option<U256> {
+    match value {
+        Value::Number(number) => number.as_u64().map(U256::from),
+        Value::String(string) => match string.strip_prefix("0x") {
+            Some(hex) => U256::from_str_radix(hex, 16).ok(),
+            None => U256::from_dec_str(string).ok(),
+        },
+        _ => None,
+    }
+}
+
+// Negative numbers are encoded in two's complement
+fn parse_int(value: &Value) -> Option<U256> {
+    let (negative, magnitude) = match value {
+        Value::Number(number) => {
+            let number = number.as_i64()?;
+            (number < 0, U256::from(number.unsigned_abs()))
+        }
+        Value::String(string) => match string.strip_prefix('-') {
+            Some(magnitude) => (true, parse_uint(&Value::from(magnitude))?),
+            None => (false, parse_uint(value)?),
+        },
+        _ => return None,
+    };
+    if negative {
+        Some((!magnitude).overflowing_add(U256::one()).0)
+    } else {
+        Some(magnitude)
+    }
+}
+
+/// An error type collecting what can go wrong when hashing typed data
+#[derive(Debug, Error)]
+pub enum Eip712Error {
+    #[error("Eip712 Error: Unknown type {0}")]
+    UnknownType(String),
+    #[error("Eip712 Error: Invalid value {value} for type {field_type}")]
+    InvalidValue { field_type: String, value: Value },
+    #[error("Eip712 Error: {0}")]
+    Signing(#[from] SigningError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Example of EIP-712
+    fn mail() -> TypedData {
+        serde_json::from_str(
+            r#"{
+                "types": {
+                    "EIP712Domain": [
+                        {"name": "name", "type": "string"},
+                        {"name": "version", "type": "string"},
+                        {"name": "chainId", "type": "uint256"},
+                        {"name": "verifyingContract", "type": "address"}
+                    ],
+                    "Person": [
+                        {"name": "name", "type": "string"},
+                        {"name": "wallet", "type": "address"}
+                    ],
+                    "Mail": [
+                        {"name": "from", "type": "Person"},
+                        {"name": "to", "type": "Person"},
+                        {"name": "contents", "type": "string"}
+                    ]
+                },
+                "primaryType": "Mail",
+                "domain": {
+                    "name": "Ether Mail",
+                    "version": "1",
+                    "chainId": 1,
+                    "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+                },
+                "message": {
+                    "from": {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},
+                    "to": {"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},
+                    "contents": "Hello, Bob!"
+                }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_eip712_hash() {
+        let typed_data = mail();
+        assert_eq!(
+            encode_type(&typed_data, "Mail").unwrap(),
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+        assert_eq!(
+            domain_separator(&typed_data).unwrap(),
+            H256::from_str("0xf2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f")
+                .unwrap()
+        );
+        assert_eq!(
+            hash_struct(&typed_data, "Mail", &typed_data.message).unwrap(),
+            H256::from_str("0xc52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e")
+                .unwrap()
+        );
+        assert_eq!(
+            hash_typed_data(&typed_data).unwrap(),
+            H256::from_str("0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2")
+                .unwrap()
+        );
+
+        // The domain type is derived from the domain if missing
+        let mut without_domain_type = typed_data.clone();
+        without_domain_type.types.remove(DOMAIN_TYPE);
+        assert_eq!(
+            domain_separator(&without_domain_type).unwrap(),
+            domain_separator(&typed_data).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_eip712_sign() {
+        let private_key = PrivateKey::NonPrefixed(H256::from(keccak256(b"cow")));
+        let signature = sign_typed_data(&mail(), &private_key).unwrap();
+        assert_eq!(
+            signature,
+            Signature {
+                r: H256::from_str(
+                    "0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d"
+                )
+                .unwrap(),
+                s: H256::from_str(
+                    "0x07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562"
+                )
+                .unwrap(),
+                v: 28,
+            }
+        );
+    }
+
+    #[test]
+    fn test_eip712_values() {
+        let typed_data = mail();
+        assert_eq!(
+            encode_value(&typed_data, "int8", &Value::from(-1)).unwrap(),
+            [0xff; 32]
+        );
+        assert_eq!(
+            encode_value(&typed_data, "uint256", &Value::from("0x10")).unwrap(),
+            encode_value(&typed_data, "uint256", &Value::from(16)).unwrap()
+        );
+        assert_eq!(
+            encode_value(&typed_data, "bytes4", &Value::from("0x01020304")).unwrap()[..5],
+            [1, 2, 3, 4, 0]
+        );
+        assert_eq!(
+            encode_value(&typed_data, "uint256[]", &serde_json::json!([1, 2])).unwrap(),
+            keccak256(&[uint_word(1.into()), uint_word(2.into())].concat())
+        );
+        assert!(matches!(
+            encode_value(&typed_data, "Letter", &Value::Null),
+            Err(Eip712Error::UnknownType(_))
+        ));
+        assert!(matches!(
+            encode_value(&typed_data, "bool", &Value::from("yes")),
+            Err(Eip712Error::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_eip712_sizes() {
+        let typed_data = mail();
+        assert!(matches!(
+            encode_value(&typed_data, "bytes4", &Value::from("0x010203")),
+            Err(Eip712Error::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            encode_value(&typed_data, "bytes4", &Value::from("0x0102030405")),
+            Err(Eip712Error::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            encode_value(&typed_data, "bytes33", &Value::from("0x01")),
+            Err(Eip712Error::UnknownType(_))
+        ));
+        assert_eq!(
+            encode_value(&typed_data, "uint256[2]", &serde_json::json!([1, 2])).unwrap(),
+            encode_value(&typed_data, "uint256[]", &serde_json::json!([1, 2])).unwrap()
+        );
+        assert!(matches!(
+            encode_value(&typed_data, "uint256[3]", &serde_json::json!([1, 2])),
+            Err(Eip712Error::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            encode_value(&typed_data, "uint256[x]", &serde_json::json!([1, 2])),
+            Err(Eip712Error::UnknownType(_))
+        ));
+    }
+}
@@ -0,0 +1,300 @@
+//! Resolution of ENS names like `vitalik.eth`
+//!
+//! Names are resolved by querying the ENS registry for the resolver of a name and then the
+//! resolver for the record, both with [eth_call](crate::rpc::eth_call). The registry address
+//! can be configured, e.g. for a registry deployed on a local development chain.
+//!
+//! Names are expected to be normalized already. This module only lowercases ASCII characters
+//! and does not implement the full UTS-46 normalization.
+//!
+//! The RPCs and contract helpers of this crate only take addresses. A [NameOrAddress], e.g. parsed
+//! from user input, has to be resolved first with [Ens::resolve].
+
+use crate::abi::{encode_call, AbiError, Decoder, Token};
+use crate::rpc;
+use crate::transport::Request;
+use crate::types::{BlockId, BlockParameter, Bytes, Call, H160, H256};
+use crate::utils::{keccak256, selector};
+use crate::{Connector, ConnectorError};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Address of the ENS registry on mainnet and most testnets
+pub const ENS_REGISTRY_ADDRESS: H160 = H160([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x0c, 0x2e, 0x07, 0x4e, 0xc6, 0x9a, 0x0d, 0xfb, 0x29, 0x97, 0xba,
+    0x6c, 0x7d, 0x2e, 0x1e,
+]);
+
+/// Computes the namehash of an ENS name, which identifies the name in the registry
+///
+/// The empty name is the root node. Names with empty labels like `foo..eth` are rejected.
+pub fn namehash(name: &str) -> Result<H256, EnsError> {
+    if name.is_empty() {
+        return Ok(H256::zero());
+    }
+    let name = name.to_ascii_lowercase();
+    let mut node = [0u8; 32];
+    for label in name.rsplit('.') {
+        if label.is_empty() {
+            return Err(EnsError::InvalidName(name));
+        }
+        let mut input = node.to_vec();
+        input.extend_from_slice(&keccak256(label.as_bytes()));
+        node = keccak256(&input);
+    }
+    Ok(H256::from(node))
+}
+
+/// Either an address or an ENS name which resolves to an address
+///
+/// Use [Ens::resolve] to get the address before passing it on.
+#[derive(Clone, Debug, PartialEq)]
+pub enum NameOrAddress {
+    Name(String),
+    Address(H160),
+}
+
+impl From<H160> for NameOrAddress {
+    fn from(address: H160) -> Self {
+        NameOrAddress::Address(address)
+    }
+}
+
+impl From<&str> for NameOrAddress {
+    fn from(name: &str) -> Self {
+        NameOrAddress::Name(String::from(name))
+    }
+}
+
+impl FromStr for NameOrAddress {
+    type Err = std::convert::Infallible;
+
+    /// Parses hex addresses as [Address](Self::Address) and everything else as [Name](Self::Name)
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let is_address = value.len() == 42 && value.starts_with("0x");
+        match H160::from_str(value) {
+            Ok(address) if is_address => Ok(NameOrAddress::Address(address)),
+            _ => Ok(NameOrAddress::Name(String::from(value))),
+        }
+    }
+}
+
+/// Resolves names with an ENS registry
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ens {
+    pub registry: H160,
+}
+
+impl Ens {
+    /// Uses the registry at `registry`, which defaults to [ENS_REGISTRY_ADDRESS]
+    pub fn new(registry: Option<H160>) -> Self {
+        Ens {
+            registry: registry.unwrap_or(ENS_REGISTRY_ADDRESS),
+        }
+    }
+
+    /// Returns the resolver of `name`, failing if none is set
+    pub fn resolver<T: Request>(
+        &self,
+        connector: &mut Connector<T>,
+        name: &str,
+        block_param: Option<BlockParameter>,
+    ) -> Result<H160, EnsError> {
+        let node = namehash(name)?;
+        let output = call(
+            connector,
+            self.registry,
+            "resolver(bytes32)",
+            &[Token::FixedBytes(node.as_bytes().to_vec())],
+            block_param,
+        )?;
+        let resolver = Decoder::new(&output.0).address(0)?;
+        if resolver.is_zero() {
+            return Err(EnsError::NoResolver(String::from(name)));
+        }
+        Ok(resolver)
+    }
+
+    /// Resolves `name` to an address, failing if none is set
+    pub fn address<T: Request>(
+        &self,
+        connector: &mut Connector<T>,
+        name: &str,
+        block_param: Option<BlockParameter>,
+    ) -> Result<H160, EnsError> {
+        let output = self.resolve_record(connector, name, "addr(bytes32)", &[], block_param)?;
+        let address = Decoder::new(&output.0).address(0)?;
+        if address.is_zero() {
+            return Err(EnsError::NoAddress(String::from(name)));
+        }
+        Ok(address)
+    }
+
+    /// Returns the address of an address or a resolved name
+    pub fn resolve<T: Request>(
+        &self,
+        connector: &mut Connector<T>,
+        name_or_address: &NameOrAddress,
+        block_param: Option<BlockParameter>,
+    ) -> Result<H160, EnsError> {
+        match name_or_address {
+            NameOrAddress::Address(address) => Ok(*address),
+            NameOrAddress::Name(name) => self.address(connector, name, block_param),
+        }
+    }
+
+    /// Looks up the primary name of `address` with reverse resolution
+    ///
+    /// Anyone can claim any name in reverse resolution, so the name is only returned if it
+    /// resolves back to `address`.
+    pub fn name<T: Request>(
+        &self,
+        connector: &mut Connector<T>,
+        address: H160,
+        block_param: Option<BlockParameter>,
+    ) -> Result<String, EnsError> {
+        let reverse_name = hex::encode(address.as_bytes()) + ".addr.reverse";
+        let output =
+            self.resolve_record(connector, &reverse_name, "name(bytes32)", &[], block_param)?;
+        let name = Decoder::new(&output.0).string(0)?;
+        if self.address(connector, &name, block_param)? != address {
+            return Err(EnsError::ReverseMismatch(name));
+        }
+        Ok(name)
+    }
+
+    /// Returns the text record `key` of `name`, like `url` or `com.github`
+    pub fn text<T: Request>(
+        &self,
+        connector: &mut Connector<T>,
+        name: &str,
+        key: &str,
+        block_param: Option<BlockParameter>,
+    ) -> Result<String, EnsError> {
+        let key = [Token::String(String::from(key))];
+        let output =
+            self.resolve_record(connector, name, "text(bytes32,string)", &key, block_param)?;
+        Ok(Decoder::new(&output.0).string(0)?)
+    }
+
+    /// Returns the EIP-1577 content hash of `name`
+    pub fn contenthash<T: Request>(
+        &self,
+        connector: &mut Connector<T>,
+        name: &str,
+        block_param: Option<BlockParameter>,
+    ) -> Result<Bytes, EnsError> {
+        let output =
+            self.resolve_record(connector, name, "contenthash(bytes32)", &[], block_param)?;
+        Ok(Bytes(Decoder::new(&output.0).bytes(0)?))
+    }
+
+    // Calls `signature` on the resolver of `name` with the namehash followed by `arguments`
+    fn resolve_record<T: Request>(
+        &self,
+        connector: &mut Connector<T>,
+        name: &str,
+        signature: &str,
+        arguments: &[Token],
+        block_param: Option<BlockParameter>,
+    ) -> Result<Bytes, EnsError> {
+        let resolver = self.resolver(connector, name, block_param)?;
+        let mut tokens = vec![Token::FixedBytes(namehash(name)?.as_bytes().to_vec())];
+        tokens.extend_from_slice(arguments);
+        call(connector, resolver, signature, &tokens, block_param)
+    }
+}
+
+impl Default for Ens {
+    fn default() -> Self {
+        Ens::new(None)
+    }
+}
+
+fn call<T: Request>(
+    connector: &mut Connector<T>,
+    to: H160,
+    signature: &str,
+    tokens: &[Token],
+    block_param: Option<BlockParameter>,
+) -> Result<Bytes, EnsError> {
+    let call = Call {
+        to,
+        data: Some(encode_call(selector(signature), tokens)),
+        ..Default::default()
+    };
+    Ok(connector.call(rpc::eth_call(
+        call,
+        block_param.map(BlockId::from),
+        None,
+        None,
+    ))?)
+}
+
+/// An error type collecting what can go wrong when resolving names
+#[derive(Debug, Error)]
+pub enum EnsError {
+    #[error("Ens Error: {0}")]
+    Connector(Box<ConnectorError>),
+    #[error("Ens Error: Unable to decode output: {0}")]
+    Abi(#[from] AbiError),
+    #[error("Ens Error: No resolver set for {0}")]
+    NoResolver(String),
+    #[error("Ens Error: No address set for {0}")]
+    NoAddress(String),
+    #[error("Ens Error: Reverse record {0} does not resolve to the address")]
+    ReverseMismatch(String),
+    #[error("Ens Error: Name {0} contains an empty label")]
+    InvalidName(String),
+}
+
+impl From<ConnectorError> for EnsError {
+    fn from(err: ConnectorError) -> Self {
+        EnsError::Connector(Box::new(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ens_namehash() {
+        assert_eq!(namehash("").unwrap(), H256::zero());
+        assert_eq!(
+            namehash("eth").unwrap(),
+            H256::from_str("0x93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae")
+                .unwrap()
+        );
+        assert_eq!(
+            namehash("foo.eth").unwrap(),
+            H256::from_str("0xde9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f")
+                .unwrap()
+        );
+        assert_eq!(namehash("Foo.ETH").unwrap(), namehash("foo.eth").unwrap());
+        for name in ["foo..eth", ".eth", "foo.eth."] {
+            assert!(matches!(namehash(name), Err(EnsError::InvalidName(_))));
+        }
+        assert_eq!(
+            ENS_REGISTRY_ADDRESS,
+            H160::from_str("0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_ens_name_or_address() {
+        assert_eq!(
+            NameOrAddress::from_str("0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e").unwrap(),
+            NameOrAddress::Address(ENS_REGISTRY_ADDRESS)
+        );
+        assert_eq!(
+            NameOrAddress::from_str("vitalik.eth").unwrap(),
+            NameOrAddress::Name(String::from("vitalik.eth"))
+        );
+        // Hex strings without prefix are treated as names
+        assert_eq!(
+            NameOrAddress::from_str("00000000000C2E074eC69A0dFb2997BA6C7d2e1e").unwrap(),
+            NameOrAddress::Name(String::from("00000000000C2E074eC69A0dFb2997BA6C7d2e1e"))
+        );
+    }
+}
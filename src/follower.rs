@@ -0,0 +1,433 @@
+//! Following the head of the chain across reorgs
+//!
+//! A [ChainFollower] keeps a window of the most recent block headers together with their logs.
+//! Every new head is linked to the window by its `parent_hash`, fetching missed ancestors if
+//! necessary. When the head belongs to another branch, the blocks after the common ancestor are
+//! [reverted](ChainEvent::Reverted) before the new branch is [applied](ChainEvent::Applied).
+//!
+//! Heads can be pushed from a [newHeads subscription](crate::rpc::sub::eth_subscribe_new_heads)
+//! with [process](ChainFollower::process), or fetched by polling with
+//! [poll](ChainFollower::poll), which also works over http.
+//!
+//! At most `depth` blocks are fetched to link a head. If the head cannot be linked, because the
+//! reorg is deeper than the window or too many blocks were missed, an error is returned and the
+//! follower keeps its window. [reset](ChainFollower::reset) re-anchors the follower at the next
+//! head. Blocks in between are not reported, so logs have to be rescanned from the last block
+//! known to be final, e.g. with a [LogScanner](crate::logs::LogScanner).
+//!
+//! ```no_run
+//! use ethane::follower::{ChainEvent, ChainFollower};
+//! use ethane::rpc::sub::eth_subscribe_new_heads;
+//! use ethane::Connector;
+//!
+//! let mut connector = Connector::websocket("ws://127.0.0.1:8546", None).unwrap();
+//! let mut subscription = connector.subscribe(eth_subscribe_new_heads()).unwrap();
+//! let mut follower = ChainFollower::new(&mut connector, None);
+//! loop {
+//!     let header = subscription.next_item().unwrap();
+//!     for event in follower.process(header).unwrap() {
+//!         match event {
+//!             ChainEvent::Applied { header, .. } => println!("Applied {:?}", header.hash),
+//!             ChainEvent::Reverted { header, .. } => println!("Reverted {:?}", header.hash),
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::rpc::Rpc;
+use crate::transport::Request;
+use crate::types::{BlockHeader, BlockParameter, FilterSubscription, HashOrLog, Log, H256};
+use crate::{Connector, ConnectorError};
+use log::debug;
+use serde::Serialize;
+use std::collections::VecDeque;
+use thiserror::Error;
+
+/// The number of blocks kept by default, which is the deepest reorg that can be handled
+pub const DEFAULT_DEPTH: usize = 64;
+
+/// A change of the chain, together with the logs matching the filter of the follower
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChainEvent {
+    /// A block became part of the chain, with its logs in order
+    Applied { header: BlockHeader, logs: Vec<Log> },
+    /// A block was removed by a reorg, with its logs in reverse order and `removed` set
+    Reverted { header: BlockHeader, logs: Vec<Log> },
+}
+
+/// Follows the chain and turns new heads into [ChainEvent]s
+///
+/// The follower starts at the first head it sees. Blocks before it are not fetched, use a
+/// [LogScanner](crate::logs::LogScanner) to catch up on older logs.
+pub struct ChainFollower<'a, T: Request> {
+    connector: &'a mut Connector<T>,
+    filter: Option<FilterSubscription>,
+    depth: usize,
+    window: VecDeque<(BlockHeader, Vec<Log>)>,
+}
+
+impl<'a, T: Request> ChainFollower<'a, T> {
+    /// Fetches blocks and logs with `connector`. Logs are only fetched if a `filter` is given.
+    pub fn new(connector: &'a mut Connector<T>, filter: Option<FilterSubscription>) -> Self {
+        ChainFollower {
+            connector,
+            filter,
+            depth: DEFAULT_DEPTH,
+            window: VecDeque::new(),
+        }
+    }
+
+    /// Sets the number of blocks kept, defaults to [DEFAULT_DEPTH]
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth.max(1);
+        self
+    }
+
+    /// Drops the window, so that the next head is taken as new starting point
+    ///
+    /// Use this to recover from [ReorgTooDeep](ChainFollowerError::ReorgTooDeep) and
+    /// [TooManyMissedBlocks](ChainFollowerError::TooManyMissedBlocks). No events are returned for
+    /// the dropped blocks.
+    pub fn reset(&mut self) {
+        debug!("Resetting window of {} blocks", self.window.len());
+        self.window.clear();
+    }
+
+    /// Returns the current head of the followed chain
+    pub fn head(&self) -> Option<&BlockHeader> {
+        self.window.back().map(|(header, _)| header)
+    }
+
+    /// Fetches the latest block and processes it
+    pub fn poll(&mut self) -> Result<Vec<ChainEvent>, ChainFollowerError> {
+        let header = self
+            .connector
+            .call(header_by_number(BlockParameter::Latest))?
+            .ok_or(ChainFollowerError::UnknownBlock(None))?;
+        self.process(header)
+    }
+
+    /// Links a new head to the followed chain and returns the resulting events
+    ///
+    /// Heads which are already part of the window are ignored. Nothing changes if an error is
+    /// returned, so the head can be processed again.
+    pub fn process(&mut self, header: BlockHeader) -> Result<Vec<ChainEvent>, ChainFollowerError> {
+        let hash = header.hash.ok_or(ChainFollowerError::MissingHash)?;
+        if self.position(hash).is_some() {
+            return Ok(Vec::new());
+        }
+
+        // Walk back from the new head until reaching a block of the window
+        let mut branch = vec![header];
+        let ancestor = loop {
+            let oldest = branch.last().unwrap();
+            if self.window.is_empty() {
+                break None;
+            }
+            if let Some(position) = self.position(oldest.parent_hash) {
+                break Some(position);
+            }
+            let lowest = self.window.front().and_then(|(header, _)| header.number);
+            if oldest.number.is_none() || oldest.number <= lowest {
+                return Err(ChainFollowerError::ReorgTooDeep(self.depth));
+            }
+            if branch.len() >= self.depth {
+                return Err(ChainFollowerError::TooManyMissedBlocks(self.depth));
+            }
+            let parent = self
+                .connector
+                .call(header_by_hash(oldest.parent_hash))?
+                .ok_or(ChainFollowerError::UnknownBlock(Some(oldest.parent_hash)))?;
+            branch.push(parent);
+        };
+
+        let mut applied = Vec::with_capacity(branch.len());
+        for header in branch.into_iter().rev() {
+            let logs = self.logs(&header)?;
+            applied.push((header, logs));
+        }
+
+        let mut events = Vec::new();
+        let keep = ancestor.map_or(0, |position| position + 1);
+        while self.window.len() > keep {
+            let (header, mut logs) = self.window.pop_back().unwrap();
+            debug!("Reverting block {:?}", header.hash);
+            logs.reverse();
+            for log in logs.iter_mut() {
+                log.removed = true;
+            }
+            events.push(ChainEvent::Reverted { header, logs });
+        }
+        for (header, logs) in applied {
+            self.window.push_back((header.clone(), logs.clone()));
+            events.push(ChainEvent::Applied { header, logs });
+        }
+        while self.window.len() > self.depth {
+            self.window.pop_front();
+        }
+        Ok(events)
+    }
+
+    fn position(&self, hash: H256) -> Option<usize> {
+        self.window
+            .iter()
+            .position(|(header, _)| header.hash == Some(hash))
+    }
+
+    fn logs(&mut self, header: &BlockHeader) -> Result<Vec<Log>, ChainFollowerError> {
+        let (filter, block_hash) = match (&self.filter, header.hash) {
+            (Some(filter), Some(block_hash)) => (filter, block_hash),
+            (Some(_), None) => return Err(ChainFollowerError::MissingHash),
+            (None, _) => return Ok(Vec::new()),
+        };
+        self.connector
+            .call(logs_by_block_hash(filter, block_hash))?
+            .into_iter()
+            .map(|item| match item {
+                HashOrLog::Log(log) => Ok(log),
+                HashOrLog::H256(hash) => Err(ChainFollowerError::UnexpectedHash(hash)),
+            })
+            .collect()
+    }
+}
+
+// Block headers are requested like blocks, the transaction hashes are ignored
+fn header_by_number(block_param: BlockParameter) -> Rpc<Option<BlockHeader>> {
+    let mut rpc = Rpc::new("eth_getBlockByNumber");
+    rpc.add_param(block_param);
+    rpc.add_param(false);
+    rpc
+}
+
+fn header_by_hash(block_hash: H256) -> Rpc<Option<BlockHeader>> {
+    let mut rpc = Rpc::new("eth_getBlockByHash");
+    rpc.add_param(block_hash);
+    rpc.add_param(false);
+    rpc
+}
+
+// The filter of eth_getLogs for the logs of a single block as defined in EIP-234
+#[derive(Debug, Serialize)]
+struct BlockHashFilter<'a> {
+    #[serde(flatten)]
+    filter: &'a FilterSubscription,
+    #[serde(rename = "blockHash")]
+    block_hash: H256,
+}
+
+fn logs_by_block_hash(filter: &FilterSubscription, block_hash: H256) -> Rpc<Vec<HashOrLog>> {
+    let mut rpc = Rpc::new("eth_getLogs");
+    rpc.add_param(BlockHashFilter { filter, block_hash });
+    rpc
+}
+
+/// An error type collecting what can go wrong when following the chain
+#[derive(Debug, Error)]
+pub enum ChainFollowerError {
+    #[error("Chain Follower Error: {0}")]
+    Connector(Box<ConnectorError>),
+    #[error("Chain Follower Error: Block header without hash")]
+    MissingHash,
+    #[error("Chain Follower Error: Unable to fetch block {0:?}")]
+    UnknownBlock(Option<H256>),
+    #[error("Chain Follower Error: Reorg is deeper than {0} blocks")]
+    ReorgTooDeep(usize),
+    #[error("Chain Follower Error: More than {0} blocks were missed")]
+    TooManyMissedBlocks(usize),
+    #[error("Chain Follower Error: Expected logs but got hash {0:?}")]
+    UnexpectedHash(H256),
+}
+
+impl From<ConnectorError> for ChainFollowerError {
+    fn from(err: ConnectorError) -> Self {
+        ChainFollowerError::Connector(Box::new(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::TransportError;
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+
+    // Serves blocks by hash and number, each with one log
+    struct MockNode {
+        blocks: HashMap<H256, Value>,
+        latest: H256,
+    }
+
+    impl MockNode {
+        fn add_block(&mut self, id: u64, parent: u64, number: u64) -> BlockHeader {
+            let block = json!({
+                "number": format!("{:#x}", number),
+                "hash": hash(id),
+                "parentHash": hash(parent),
+                "transactionsRoot": H256::zero(),
+                "stateRoot": H256::zero(),
+                "receiptsRoot": H256::zero(),
+                "difficulty": "0x0",
+                "sha3Uncles": H256::zero(),
+                "miner": "0x0000000000000000000000000000000000000000",
+                "gasLimit": "0x0",
+                "gasUsed": "0x0",
+                "extraData": "0x",
+                "timestamp": "0x0",
+                "transactions": []
+            });
+            self.blocks.insert(hash(id), block.clone());
+            self.latest = hash(id);
+            serde_json::from_value(block).unwrap()
+        }
+    }
+
+    impl Request for MockNode {
+        fn request(&mut self, cmd: String) -> Result<String, TransportError> {
+            let request: Value = serde_json::from_str(&cmd).unwrap();
+            let params = &request["params"];
+            let result = match request["method"].as_str().unwrap() {
+                "eth_getBlockByNumber" => self.blocks[&self.latest].clone(),
+                "eth_getBlockByHash" => {
+                    let hash = serde_json::from_value(params[0].clone()).unwrap();
+                    self.blocks.get(&hash).cloned().unwrap_or(Value::Null)
+                }
+                "eth_getLogs" => {
+                    assert!(params[0].get("fromBlock").is_none());
+                    let block = &self.blocks
+                        [&serde_json::from_value(params[0]["blockHash"].clone()).unwrap()];
+                    json!([{
+                        "address": "0x0000000000000000000000000000000000000001",
+                        "topics": [],
+                        "data": "0x",
+                        "blockHash": block["hash"],
+                        "blockNumber": block["number"],
+                        "transactionHash": H256::zero(),
+                        "transactionIndex": "0x0",
+                        "logIndex": "0x0",
+                        "removed": false
+                    }])
+                }
+                method => panic!("Unexpected method {}", method),
+            };
+            Ok(json!({"jsonrpc": "2.0", "id": request["id"], "result": result}).to_string())
+        }
+    }
+
+    fn hash(id: u64) -> H256 {
+        H256::from_low_u64_be(id)
+    }
+
+    fn connector() -> Connector<MockNode> {
+        Connector::with_transport(MockNode {
+            blocks: HashMap::new(),
+            latest: H256::zero(),
+        })
+    }
+
+    // Summarizes events as (applied, block id, removed flags of the logs)
+    fn summary(events: Vec<ChainEvent>) -> Vec<(bool, u64, Vec<bool>)> {
+        events
+            .into_iter()
+            .map(|event| {
+                let (applied, header, logs) = match event {
+                    ChainEvent::Applied { header, logs } => (true, header, logs),
+                    ChainEvent::Reverted { header, logs } => (false, header, logs),
+                };
+                let removed = logs.iter().map(|log| log.removed).collect();
+                (applied, header.hash.unwrap().to_low_u64_be(), removed)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_follower_reorg() {
+        let mut node = connector();
+        let headers: Vec<BlockHeader> = (1..=3)
+            .map(|id| node.transport_mut().add_block(id, id - 1, id))
+            .collect();
+        // A competing branch forking off after block 2
+        let fork = node.transport_mut().add_block(13, 2, 3);
+        let head = node.transport_mut().add_block(14, 13, 4);
+
+        let mut follower = ChainFollower::new(&mut node, Some(FilterSubscription::default()));
+        for header in headers {
+            assert_eq!(summary(follower.process(header).unwrap()).len(), 1);
+        }
+        assert_eq!(
+            summary(follower.process(head.clone()).unwrap()),
+            vec![
+                (false, 3, vec![true]),
+                (true, 13, vec![false]),
+                (true, 14, vec![false])
+            ]
+        );
+        assert!(follower.process(head).unwrap().is_empty());
+        assert!(follower.process(fork).unwrap().is_empty());
+        assert_eq!(follower.head().unwrap().hash, Some(hash(14)));
+    }
+
+    #[test]
+    fn test_follower_poll() {
+        let mut node = connector();
+        node.transport_mut().add_block(1, 0, 1);
+        let mut follower = ChainFollower::new(&mut node, None);
+        assert_eq!(summary(follower.poll().unwrap()), vec![(true, 1, vec![])]);
+        assert!(follower.poll().unwrap().is_empty());
+
+        // Missed blocks are fetched
+        follower.connector.transport_mut().add_block(2, 1, 2);
+        follower.connector.transport_mut().add_block(3, 2, 3);
+        assert_eq!(
+            summary(follower.poll().unwrap()),
+            vec![(true, 2, vec![]), (true, 3, vec![])]
+        );
+    }
+
+    #[test]
+    fn test_follower_reorg_too_deep() {
+        let mut node = connector();
+        let headers: Vec<BlockHeader> = (1..=4)
+            .map(|id| node.transport_mut().add_block(id, id - 1, id))
+            .collect();
+        node.transport_mut().add_block(12, 1, 2);
+        node.transport_mut().add_block(13, 12, 3);
+        let head = node.transport_mut().add_block(14, 13, 4);
+
+        let mut follower = ChainFollower::new(&mut node, None).with_depth(2);
+        for header in headers {
+            follower.process(header).unwrap();
+        }
+        assert!(matches!(
+            follower.process(head.clone()),
+            Err(ChainFollowerError::ReorgTooDeep(2))
+        ));
+        assert_eq!(follower.head().unwrap().hash, Some(hash(4)));
+
+        follower.reset();
+        assert_eq!(
+            summary(follower.process(head).unwrap()),
+            vec![(true, 14, vec![])]
+        );
+        assert_eq!(follower.head().unwrap().hash, Some(hash(14)));
+    }
+
+    #[test]
+    fn test_follower_too_many_missed_blocks() {
+        let mut node = connector();
+        node.transport_mut().add_block(1, 0, 1);
+        let mut follower = ChainFollower::new(&mut node, None).with_depth(2);
+        follower.poll().unwrap();
+
+        for id in 2..=5 {
+            follower.connector.transport_mut().add_block(id, id - 1, id);
+        }
+        assert!(matches!(
+            follower.poll(),
+            Err(ChainFollowerError::TooManyMissedBlocks(2))
+        ));
+        assert_eq!(follower.head().unwrap().hash, Some(hash(1)));
+
+        follower.reset();
+        assert_eq!(summary(follower.poll().unwrap()), vec![(true, 5, vec![])]);
+    }
+}
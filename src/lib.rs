@@ -1,7 +1,8 @@
 //! Ethane is an alternative web3 implementation with the aim of being slim and simple.
 //! It does not depend on futures or any executors. It currently supports http and
 //! websockets (both plain and TLS) and inter process communication via Unix domain sockets (Unix only). For
-//! http and websockets it also supports Http Basic and Bearer Authentication.
+//! http and websockets it also supports Http Basic and Bearer Authentication, as well as the JWT
+//! authentication of the Engine API.
 //!
 //! Currently only Eth1 is supported and not all namespaces are implemented. You can have a look here
 //! ([supported RPCs](crate::rpc)) to see what is supported. Please note that the
@@ -64,12 +65,25 @@
 
 pub use connector::subscription::{Subscription, SubscriptionError};
 pub use connector::{Connector, ConnectorError};
-pub use transport::{http::Http, websocket::WebSocket, Credentials};
+pub use transport::{http::Http, jwt::JwtSecret, websocket::WebSocket, Credentials};
 
 #[cfg(target_family = "unix")]
 pub use transport::uds::Uds;
 
+pub mod abi;
 pub mod connector;
+pub mod deploy;
+pub mod eip712;
+pub mod ens;
+pub mod follower;
+pub mod logs;
+pub mod multicall;
+pub mod proof;
 pub mod rpc;
+pub mod signing;
+pub mod storage;
+pub mod tokens;
 pub mod transport;
 pub mod types;
+pub mod units;
+pub mod utils;
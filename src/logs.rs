@@ -0,0 +1,454 @@
+//! Scanning logs over wide block ranges
+//!
+//! Most nodes and providers reject [eth_get_logs](crate::rpc::eth_get_logs) requests which span
+//! too many blocks or return too many logs. A [LogScanner] splits the range of a [Filter] into
+//! chunks, halving the chunk size whenever the node reports such a limit and doubling it again
+//! after successful requests. Logs are yielded in order, and a [LogCheckpoint] taken at any point
+//! allows to resume an interrupted scan without missing or repeating logs.
+
+use crate::rpc;
+use crate::transport::Request;
+use crate::types::{BlockParameter, Filter, HashOrLog, Log, H256, U64};
+use crate::{Connector, ConnectorError};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use thiserror::Error;
+
+/// The number of blocks requested at first
+pub const DEFAULT_CHUNK_SIZE: u64 = 1000;
+/// The number of blocks up to which chunks grow
+pub const DEFAULT_MAX_CHUNK_SIZE: u64 = 10_000;
+
+// Parts of the error messages with which geth and common providers reject log requests for
+// returning too many results or spanning too many blocks. Rate limits are not matched, as smaller
+// chunks would not help there.
+const LIMIT_MESSAGES: [&str; 7] = [
+    "query returned more than",
+    "response size exceeded",
+    "exceeds max results",
+    "exceed maximum block range",
+    "exceeds max block range",
+    "block range is too wide",
+    "block range too large",
+];
+
+/// The position of a scan, which can be persisted to resume the scan later
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LogCheckpoint {
+    /// The first block with logs which were not yielded yet
+    pub from_block: U64,
+    pub to_block: U64,
+    /// The number of logs of `from_block` which were already yielded
+    pub skip: usize,
+    pub chunk_size: u64,
+}
+
+/// An iterator over the logs matching a filter, which requests them chunk by chunk
+///
+/// The block range is resolved when the scanner is created, so `latest` refers to the head at
+/// that time. After an error the iterator ends, and the scan can be continued from its
+/// [checkpoint](Self::checkpoint).
+pub struct LogScanner<'a, T: Request> {
+    connector: &'a mut Connector<T>,
+    filter: Filter,
+    next_block: u64,
+    to_block: u64,
+    skip: usize,
+    chunk_size: u64,
+    max_chunk_size: u64,
+    buffer: VecDeque<(u64, Log)>,
+    yielded: Option<(u64, usize)>,
+    failed: bool,
+}
+
+impl<'a, T: Request> LogScanner<'a, T> {
+    /// Scans the range of `filter`, where a missing `from_block` or `to_block` means `latest`
+    pub fn new(connector: &'a mut Connector<T>, filter: Filter) -> Result<Self, LogScannerError> {
+        let from_block = resolve_block(connector, filter.from_block)?;
+        let to_block = resolve_block(connector, filter.to_block)?;
+        Ok(Self::scanner(connector, filter, from_block, to_block, 0))
+    }
+
+    /// Continues a scan of `filter` from a checkpoint, ignoring the range of `filter`
+    pub fn resume(
+        connector: &'a mut Connector<T>,
+        filter: Filter,
+        checkpoint: LogCheckpoint,
+    ) -> Self {
+        let scanner = Self::scanner(
+            connector,
+            filter,
+            checkpoint.from_block.as_u64(),
+            checkpoint.to_block.as_u64(),
+            checkpoint.skip,
+        );
+        scanner.with_chunk_size(checkpoint.chunk_size)
+    }
+
+    /// Sets the number of blocks of the next request, defaults to [DEFAULT_CHUNK_SIZE]
+    pub fn with_chunk_size(mut self, chunk_size: u64) -> Self {
+        self.chunk_size = chunk_size.clamp(1, self.max_chunk_size.max(1));
+        self
+    }
+
+    /// Sets the number of blocks up to which chunks grow, defaults to [DEFAULT_MAX_CHUNK_SIZE]
+    pub fn with_max_chunk_size(mut self, max_chunk_size: u64) -> Self {
+        self.max_chunk_size = max_chunk_size.max(1);
+        self.chunk_size = self.chunk_size.min(self.max_chunk_size);
+        self
+    }
+
+    /// Returns the position after the last yielded log
+    pub fn checkpoint(&self) -> LogCheckpoint {
+        let (from_block, skip) = match self.buffer.front() {
+            Some((block, _)) => match self.yielded {
+                Some((yielded_block, count)) if yielded_block == *block => (*block, count),
+                _ => (*block, 0),
+            },
+            None => (self.next_block, self.skip),
+        };
+        LogCheckpoint {
+            from_block: U64::from(from_block),
+            to_block: U64::from(self.to_block),
+            skip,
+            chunk_size: self.chunk_size,
+        }
+    }
+
+    fn scanner(
+        connector: &'a mut Connector<T>,
+        filter: Filter,
+        from_block: u64,
+        to_block: u64,
+        skip: usize,
+    ) -> Self {
+        LogScanner {
+            connector,
+            filter,
+            next_block: from_block,
+            to_block,
+            skip,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            max_chunk_size: DEFAULT_MAX_CHUNK_SIZE,
+            buffer: VecDeque::new(),
+            // Logs skipped on resume count as yielded for further checkpoints
+            yielded: if skip > 0 {
+                Some((from_block, skip))
+            } else {
+                None
+            },
+            failed: false,
+        }
+    }
+
+    fn fetch(&mut self, from_block: u64, to_block: u64) -> Result<(), LogScannerError> {
+        let filter = Filter {
+            from_block: Some(BlockParameter::Custom(U64::from(from_block))),
+            to_block: Some(BlockParameter::Custom(U64::from(to_block))),
+            ..self.filter.clone()
+        };
+        let mut logs = Vec::new();
+        for item in self.connector.call(rpc::eth_get_logs(filter))? {
+            match item {
+                HashOrLog::Log(log) => {
+                    let block = log
+                        .block_number
+                        .ok_or(LogScannerError::MissingBlockNumber)?;
+                    logs.push((block.as_u64(), log));
+                }
+                HashOrLog::H256(hash) => return Err(LogScannerError::UnexpectedHash(hash)),
+            }
+        }
+
+        // Drop logs which were yielded before the scan was resumed
+        let skipped = logs
+            .iter()
+            .take(self.skip)
+            .take_while(|(block, _)| *block == self.next_block)
+            .count();
+        self.buffer.extend(logs.into_iter().skip(skipped));
+        self.skip = 0;
+        Ok(())
+    }
+}
+
+impl<'a, T: Request> Iterator for LogScanner<'a, T> {
+    type Item = Result<Log, LogScannerError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((block, log)) = self.buffer.pop_front() {
+                self.yielded = match self.yielded {
+                    Some((yielded_block, count)) if yielded_block == block => {
+                        Some((block, count + 1))
+                    }
+                    _ => Some((block, 1)),
+                };
+                return Some(Ok(log));
+            }
+            if self.failed || self.next_block > self.to_block {
+                return None;
+            }
+
+            let range = self.chunk_size.min(self.to_block - self.next_block + 1);
+            let to_block = self.next_block + range - 1;
+            match self.fetch(self.next_block, to_block) {
+                Ok(()) => {
+                    self.next_block = to_block + 1;
+                    self.chunk_size = self.chunk_size.saturating_mul(2).min(self.max_chunk_size);
+                }
+                Err(LogScannerError::Connector(err)) if range > 1 && is_limit_error(&err) => {
+                    debug!("Log request for {} blocks was rejected: {}", range, err);
+                    self.chunk_size = range / 2;
+                }
+                Err(err) => {
+                    self.failed = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+}
+
+fn is_limit_error(err: &ConnectorError) -> bool {
+    match err {
+        ConnectorError::JsonRpc(err) => {
+            let message = err.message().to_ascii_lowercase();
+            LIMIT_MESSAGES
+                .iter()
+                .any(|pattern| message.contains(pattern))
+        }
+        _ => false,
+    }
+}
+
+fn resolve_block<T: Request>(
+    connector: &mut Connector<T>,
+    block_param: Option<BlockParameter>,
+) -> Result<u64, LogScannerError> {
+    let block_param = block_param.unwrap_or(BlockParameter::Latest);
+    let block = match block_param {
+        BlockParameter::Custom(number) => Some(number),
+        BlockParameter::Earliest => Some(U64::zero()),
+        BlockParameter::Latest | BlockParameter::Pending => {
+            Some(connector.call(rpc::eth_block_number())?)
+        }
+        BlockParameter::Safe | BlockParameter::Finalized => connector
+            .call(rpc::eth_get_block_by_number(Some(block_param), false))?
+            .and_then(|block| block.number),
+    };
+    block
+        .map(|number| number.as_u64())
+        .ok_or(LogScannerError::UnknownBlock(block_param))
+}
+
+/// An error type collecting what can go wrong when scanning logs
+#[derive(Debug, Error)]
+pub enum LogScannerError {
+    #[error("Log Scanner Error: {0}")]
+    Connector(Box<ConnectorError>),
+    #[error("Log Scanner Error: Unable to resolve block {0:?}")]
+    UnknownBlock(BlockParameter),
+    #[error("Log Scanner Error: Log without block number")]
+    MissingBlockNumber,
+    #[error("Log Scanner Error: Expected logs but got hash {0:?}")]
+    UnexpectedHash(H256),
+}
+
+impl From<ConnectorError> for LogScannerError {
+    fn from(err: ConnectorError) -> Self {
+        LogScannerError::Connector(Box::new(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::TransportError;
+    use serde_json::{json, Value};
+
+    // Serves `logs_per_block` logs for every block and rejects requests for more than `limit`
+    // blocks
+    struct MockNode {
+        head: u64,
+        limit: u64,
+        logs_per_block: u64,
+        requests: Vec<(u64, u64)>,
+    }
+
+    impl Request for MockNode {
+        fn request(&mut self, cmd: String) -> Result<String, TransportError> {
+            let request: Value = serde_json::from_str(&cmd).unwrap();
+            let id = request["id"].clone();
+            let block = |value: &Value| u64::from_str_radix(&value.as_str().unwrap()[2..], 16);
+            let result = match request["method"].as_str().unwrap() {
+                "eth_blockNumber" => json!(format!("{:#x}", self.head)),
+                "eth_getLogs" => {
+                    let from = block(&request["params"][0]["fromBlock"]).unwrap();
+                    let to = block(&request["params"][0]["toBlock"]).unwrap();
+                    self.requests.push((from, to));
+                    if to - from + 1 > self.limit {
+                        let error = json!({"code": -32000, "message": "query returned more than 10000 results"});
+                        return Ok(json!({"jsonrpc": "2.0", "id": id, "error": error}).to_string());
+                    }
+                    let logs: Vec<Value> = (from..=to)
+                        .flat_map(|block| (0..self.logs_per_block).map(move |index| (block, index)))
+                        .map(|(block, index)| {
+                            json!({
+                                "address": "0x0000000000000000000000000000000000000001",
+                                "topics": [],
+                                "data": "0x",
+                                "blockHash": format!("{:#066x}", block),
+                                "blockNumber": format!("{:#x}", block),
+                                "transactionHash": format!("{:#066x}", index),
+                                "transactionIndex": "0x0",
+                                "logIndex": format!("{:#x}", index),
+                                "removed": false
+                            })
+                        })
+                        .collect();
+                    json!(logs)
+                }
+                method => panic!("Unexpected method {}", method),
+            };
+            Ok(json!({"jsonrpc": "2.0", "id": id, "result": result}).to_string())
+        }
+    }
+
+    fn connector(limit: u64) -> Connector<MockNode> {
+        Connector::with_transport(MockNode {
+            head: 99,
+            limit,
+            logs_per_block: 2,
+            requests: Vec::new(),
+        })
+    }
+
+    fn position(log: &Log) -> (u64, u64) {
+        (
+            log.block_number.unwrap().as_u64(),
+            log.log_index.unwrap().as_u64(),
+        )
+    }
+
+    #[test]
+    fn test_logs_scan_adapts_chunks() {
+        let mut connector = connector(16);
+        let filter = Filter {
+            from_block: Some(BlockParameter::Earliest),
+            ..Default::default()
+        };
+        let logs: Vec<(u64, u64)> = LogScanner::new(&mut connector, filter)
+            .unwrap()
+            .with_chunk_size(10)
+            .map(|log| position(&log.unwrap()))
+            .collect();
+        let expected: Vec<(u64, u64)> = (0..100)
+            .flat_map(|block| vec![(block, 0), (block, 1)])
+            .collect();
+        assert_eq!(logs, expected);
+
+        // 10 blocks succeed, 20 are rejected and halved, then 10 and 20 again
+        let requests = &connector.transport().requests;
+        assert_eq!(&requests[..4], &[(0, 9), (10, 29), (10, 19), (20, 39)]);
+        assert_eq!(requests.len(), 18);
+    }
+
+    #[test]
+    fn test_logs_scan_resume() {
+        let mut connector = connector(100);
+        let filter = Filter {
+            from_block: Some(BlockParameter::Custom(U64::from(10))),
+            to_block: Some(BlockParameter::Custom(U64::from(19))),
+            ..Default::default()
+        };
+        let mut scanner = LogScanner::new(&mut connector, filter.clone())
+            .unwrap()
+            .with_chunk_size(4);
+        let first: Vec<(u64, u64)> = scanner
+            .by_ref()
+            .take(3)
+            .map(|log| position(&log.unwrap()))
+            .collect();
+        let checkpoint = scanner.checkpoint();
+        assert_eq!(first, vec![(10, 0), (10, 1), (11, 0)]);
+        assert_eq!(checkpoint.from_block, U64::from(11));
+        assert_eq!(checkpoint.skip, 1);
+
+        let rest: Vec<(u64, u64)> = LogScanner::resume(&mut connector, filter, checkpoint)
+            .map(|log| position(&log.unwrap()))
+            .collect();
+        assert_eq!(rest.first(), Some(&(11, 1)));
+        assert_eq!(rest.last(), Some(&(19, 1)));
+        assert_eq!(first.len() + rest.len(), 20);
+    }
+
+    #[test]
+    fn test_logs_scan_resume_twice_within_block() {
+        let mut connector = connector(100);
+        connector.transport_mut().logs_per_block = 4;
+        let filter = Filter::default();
+        let checkpoint = LogCheckpoint {
+            from_block: U64::from(11),
+            to_block: U64::from(12),
+            skip: 1,
+            chunk_size: 2,
+        };
+        let mut scanner = LogScanner::resume(&mut connector, filter.clone(), checkpoint);
+        let first: Vec<(u64, u64)> = scanner
+            .by_ref()
+            .take(1)
+            .map(|log| position(&log.unwrap()))
+            .collect();
+        let checkpoint = scanner.checkpoint();
+        assert_eq!(first, vec![(11, 1)]);
+        assert_eq!(checkpoint.from_block, U64::from(11));
+        assert_eq!(checkpoint.skip, 2);
+
+        let rest: Vec<(u64, u64)> = LogScanner::resume(&mut connector, filter, checkpoint)
+            .map(|log| position(&log.unwrap()))
+            .collect();
+        assert_eq!(rest.first(), Some(&(11, 2)));
+        assert_eq!(rest.len(), 6);
+    }
+
+    #[test]
+    fn test_logs_limit_errors() {
+        let error = |code: i32, message: &str| {
+            ConnectorError::JsonRpc(
+                serde_json::from_value(json!({"code": code, "message": message})).unwrap(),
+            )
+        };
+        assert!(is_limit_error(&error(
+            -32005,
+            "query returned more than 10000 results"
+        )));
+        assert!(is_limit_error(&error(
+            -32000,
+            "exceed maximum block range: 5000"
+        )));
+        assert!(is_limit_error(&error(
+            -32602,
+            "Log response size exceeded. You can make eth_getLogs requests with up to a 2K block range"
+        )));
+        assert!(!is_limit_error(&error(
+            -32005,
+            "daily request count exceeded, request rate limited"
+        )));
+        assert!(!is_limit_error(&error(429, "Too many requests")));
+        assert!(!is_limit_error(&error(-32000, "header not found")));
+    }
+
+    #[test]
+    fn test_logs_scan_fails_for_single_block() {
+        let mut connector = connector(0);
+        let mut scanner = LogScanner::new(&mut connector, Filter::default()).unwrap();
+        assert!(matches!(
+            scanner.next(),
+            Some(Err(LogScannerError::Connector(err))) if matches!(*err, ConnectorError::JsonRpc(_))
+        ));
+        assert!(scanner.next().is_none());
+        assert_eq!(scanner.checkpoint().from_block, U64::from(99));
+    }
+}
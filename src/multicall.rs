@@ -0,0 +1,323 @@
+//! Aggregation of many calls into a single request
+//!
+//! A [Multicall] bundles [calls](crate::types::Call) into one [eth_call](crate::rpc::eth_call) to
+//! the [Multicall3](https://github.com/mds1/multicall) contract, which is deployed at the same
+//! address on most chains. Chains without Multicall3 are served by a JSON-RPC batch instead.
+
+use crate::abi::{encode_call, AbiError, Decoder, Token};
+use crate::connector::MAX_BATCH_SIZE;
+use crate::rpc::{self, Rpc};
+use crate::transport::Request;
+use crate::types::{BlockId, BlockParameter, Bytes, Call, H160};
+use crate::{Connector, ConnectorError};
+use log::debug;
+use thiserror::Error;
+
+/// Address of the Multicall3 contract on most chains
+pub const MULTICALL3_ADDRESS: H160 = H160([
+    0xca, 0x11, 0xbd, 0xe0, 0x59, 0x77, 0xb3, 0x63, 0x11, 0x67, 0x02, 0x88, 0x62, 0xbe, 0x2a, 0x17,
+    0x39, 0x76, 0xca, 0x11,
+]);
+
+// Selector of aggregate3((address,bool,bytes)[])
+const AGGREGATE3_SELECTOR: [u8; 4] = [0x82, 0xad, 0x56, 0xcb];
+
+/// Bundles many calls into a single request
+///
+/// Only `to` and `data` of the calls are forwarded to Multicall3, because the contract executes
+/// the calls itself. Every call is allowed to fail, so the results carry a success flag per call.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Multicall {
+    address: H160,
+    calls: Vec<Call>,
+}
+
+impl Multicall {
+    /// Creates an empty multicall using the Multicall3 contract at `address`, which defaults to
+    /// [MULTICALL3_ADDRESS]
+    pub fn new(address: Option<H160>) -> Self {
+        Multicall {
+            address: address.unwrap_or(MULTICALL3_ADDRESS),
+            calls: Vec::new(),
+        }
+    }
+
+    pub fn add_call(&mut self, call: Call) {
+        self.calls.push(call);
+    }
+
+    pub fn calls(&self) -> &[Call] {
+        &self.calls
+    }
+
+    /// Executes all calls and returns their results in the order they were added
+    ///
+    /// Falls back to [call_batch](Self::call_batch) if there is no contract at the configured
+    /// address. Calls failing in the batch are reported as unsuccessful without return data.
+    pub fn call<T: Request>(
+        &self,
+        connector: &mut Connector<T>,
+        block_param: Option<BlockParameter>,
+    ) -> Result<Vec<MulticallResult>, MulticallError> {
+        if self.calls.is_empty() {
+            return Ok(Vec::new());
+        }
+        let output = connector.call(self.rpc(block_param))?;
+        // Calling an account without code succeeds without output
+        if output.0.is_empty() {
+            debug!("No multicall contract at {:?}, using batch", self.address);
+            let results = self
+                .call_batch(connector, block_param)?
+                .into_iter()
+                .map(|result| match result {
+                    Ok(return_data) => MulticallResult {
+                        success: true,
+                        return_data,
+                    },
+                    Err(_) => MulticallResult {
+                        success: false,
+                        return_data: Bytes::default(),
+                    },
+                })
+                .collect();
+            return Ok(results);
+        }
+        self.decode(&output)
+    }
+
+    /// Executes all calls as single [eth_call](crate::rpc::eth_call)s within JSON-RPC batches
+    ///
+    /// The calls are split into batches of at most [MAX_BATCH_SIZE] calls. The result of every
+    /// call carries the error of the node if the call failed.
+    pub fn call_batch<T: Request>(
+        &self,
+        connector: &mut Connector<T>,
+        block_param: Option<BlockParameter>,
+    ) -> Result<Vec<Result<Bytes, ConnectorError>>, MulticallError> {
+        let mut results = Vec::with_capacity(self.calls.len());
+        for calls in self.calls.chunks(MAX_BATCH_SIZE) {
+            let rpcs = calls
+                .iter()
+                .map(|call| rpc::eth_call(call.clone(), block_param.map(BlockId::from), None, None))
+                .collect();
+            results.extend(connector.call_batch(rpcs)?);
+        }
+        Ok(results)
+    }
+
+    /// The call to `aggregate3` of the Multicall3 contract
+    pub fn rpc(&self, block_param: Option<BlockParameter>) -> Rpc<Bytes> {
+        let calls = self
+            .calls
+            .iter()
+            .map(|call| {
+                Token::Tuple(vec![
+                    Token::Address(call.to),
+                    Token::Bool(true),
+                    Token::Bytes(call.data.clone().unwrap_or_default().0),
+                ])
+            })
+            .collect();
+        let call = Call {
+            to: self.address,
+            data: Some(encode_call(AGGREGATE3_SELECTOR, &[Token::Array(calls)])),
+            ..Default::default()
+        };
+        rpc::eth_call(call, block_param.map(BlockId::from), None, None)
+    }
+
+    /// Decodes the output of `aggregate3` into one result per call
+    pub fn decode(&self, output: &Bytes) -> Result<Vec<MulticallResult>, MulticallError> {
+        let (len, results) = Decoder::new(&output.0).array(0)?;
+        if len != self.calls.len() {
+            return Err(MulticallError::ResultCount {
+                expected: self.calls.len(),
+                found: len,
+            });
+        }
+        (0..len)
+            .map(|index| {
+                let result = results.tail(index)?;
+                Ok(MulticallResult {
+                    success: result.bool(0)?,
+                    return_data: Bytes(result.bytes(1)?),
+                })
+            })
+            .collect()
+    }
+}
+
+impl Default for Multicall {
+    fn default() -> Self {
+        Multicall::new(None)
+    }
+}
+
+/// The outcome of a single call of a [Multicall]
+///
+/// If the call failed, `return_data` contains the revert data when it was executed by
+/// Multicall3 and is empty when it was executed in a batch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MulticallResult {
+    pub success: bool,
+    pub return_data: Bytes,
+}
+
+/// An error type collecting what can go wrong with a multicall
+#[derive(Debug, Error)]
+pub enum MulticallError {
+    #[error("Multicall Error: {0}")]
+    Connector(Box<ConnectorError>),
+    #[error("Multicall Error: Unable to decode results: {0}")]
+    Abi(#[from] AbiError),
+    #[error("Multicall Error: Expected {expected} results, found {found}")]
+    ResultCount { expected: usize, found: usize },
+}
+
+impl From<ConnectorError> for MulticallError {
+    fn from(err: ConnectorError) -> Self {
+        MulticallError::Connector(Box::new(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::TransportError;
+    use crate::utils::keccak256;
+    use serde_json::{json, Value};
+    use std::str::FromStr;
+
+    // Answers every eth_call of a batch with the address called, except for the zero address,
+    // and records the batch sizes
+    struct MockNode {
+        batches: Vec<usize>,
+    }
+
+    impl Request for MockNode {
+        fn request(&mut self, cmd: String) -> Result<String, TransportError> {
+            let requests: Vec<Value> = serde_json::from_str(&cmd).unwrap();
+            self.batches.push(requests.len());
+            let responses: Vec<Value> = requests
+                .iter()
+                .map(|request| {
+                    let to = request["params"][0]["to"].clone();
+                    if to == "0x0000000000000000000000000000000000000000" {
+                        let error = json!({"code": 3, "message": "execution reverted"});
+                        json!({"jsonrpc": "2.0", "id": request["id"], "error": error})
+                    } else {
+                        json!({"jsonrpc": "2.0", "id": request["id"], "result": to})
+                    }
+                })
+                .collect();
+            Ok(json!(responses).to_string())
+        }
+    }
+
+    #[test]
+    fn test_multicall_encoding() {
+        assert_eq!(
+            keccak256(b"aggregate3((address,bool,bytes)[])")[..4],
+            AGGREGATE3_SELECTOR
+        );
+        assert_eq!(
+            MULTICALL3_ADDRESS,
+            H160::from_str("0xcA11bde05977b3631167028862bE2a173976CA11").unwrap()
+        );
+
+        let mut multicall = Multicall::default();
+        multicall.add_call(Call {
+            to: H160::from_low_u64_be(1),
+            data: Some(Bytes(vec![0xaa, 0xbb])),
+            ..Default::default()
+        });
+        let rpc = multicall.rpc(None);
+        assert_eq!(rpc.params[1], "latest");
+        assert_eq!(
+            rpc.params[0],
+            serde_json::json!({
+                "to": "0xca11bde05977b3631167028862be2a173976ca11",
+                "data": String::from("0x82ad56cb") + concat!(
+                    "0000000000000000000000000000000000000000000000000000000000000020",
+                    "0000000000000000000000000000000000000000000000000000000000000001",
+                    "0000000000000000000000000000000000000000000000000000000000000020",
+                    "0000000000000000000000000000000000000000000000000000000000000001",
+                    "0000000000000000000000000000000000000000000000000000000000000001",
+                    "0000000000000000000000000000000000000000000000000000000000000060",
+                    "0000000000000000000000000000000000000000000000000000000000000002",
+                    "aabb000000000000000000000000000000000000000000000000000000000000",
+                )
+            })
+        );
+    }
+
+    #[test]
+    fn test_multicall_decoding() {
+        let mut multicall = Multicall::new(Some(H160::from_low_u64_be(2)));
+        multicall.add_call(Call::default());
+        multicall.add_call(Call::default());
+        // [(true, 0x2a), (false, 0x)]
+        let output = Bytes::from_str(concat!(
+            "0000000000000000000000000000000000000000000000000000000000000020",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "0000000000000000000000000000000000000000000000000000000000000040",
+            "00000000000000000000000000000000000000000000000000000000000000c0",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "0000000000000000000000000000000000000000000000000000000000000040",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "2a00000000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000040",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+        ))
+        .unwrap();
+        assert_eq!(
+            multicall.decode(&output).unwrap(),
+            vec![
+                MulticallResult {
+                    success: true,
+                    return_data: Bytes(vec![0x2a]),
+                },
+                MulticallResult {
+                    success: false,
+                    return_data: Bytes::default(),
+                },
+            ]
+        );
+
+        multicall.add_call(Call::default());
+        assert!(matches!(
+            multicall.decode(&output),
+            Err(MulticallError::ResultCount {
+                expected: 3,
+                found: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_multicall_call_batch() {
+        let mut connector = Connector::with_transport(MockNode {
+            batches: Vec::new(),
+        });
+        let mut multicall = Multicall::default();
+        for index in 0..MAX_BATCH_SIZE + 2 {
+            multicall.add_call(Call {
+                to: H160::from_low_u64_be(index as u64),
+                ..Default::default()
+            });
+        }
+
+        let results = multicall.call_batch(&mut connector, None).unwrap();
+        assert_eq!(connector.transport().batches, vec![MAX_BATCH_SIZE, 2]);
+        assert_eq!(results.len(), MAX_BATCH_SIZE + 2);
+        match &results[0] {
+            Err(ConnectorError::JsonRpc(err)) => assert_eq!(err.code(), 3),
+            result => panic!("Unexpected result {:?}", result),
+        }
+        assert_eq!(
+            results[MAX_BATCH_SIZE + 1].as_ref().unwrap(),
+            &Bytes(H160::from_low_u64_be(MAX_BATCH_SIZE as u64 + 1).0.to_vec())
+        );
+    }
+}
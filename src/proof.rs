@@ -0,0 +1,401 @@
+//! Verification of Merkle-Patricia proofs returned by [eth_get_proof](crate::rpc::eth_get_proof)
+//!
+//! This allows to validate answers of untrusted nodes client-side. Obtain a trusted state root,
+//! e.g. from [Block.state_root](crate::types::Block::state_root), and check the proof against it.
+//! The proven values can then be used in place of [eth_get_balance](crate::rpc::eth_get_balance),
+//! [eth_get_storage_at](crate::rpc::eth_get_storage_at) and, together with [verify_code],
+//! [eth_get_code](crate::rpc::eth_get_code).
+
+use crate::types::{AccountProof, Bytes, StorageProof, H256, U256};
+use crate::utils::keccak256;
+use ethereum_types::BigEndianHash;
+use rlp::{DecoderError, Rlp, RlpStream};
+use thiserror::Error;
+
+/// Root hash of an empty trie, used as storage hash of accounts without storage
+pub const EMPTY_TRIE_ROOT: H256 = H256([
+    0x56, 0xe8, 0x1f, 0x17, 0x1b, 0xcc, 0x55, 0xa6, 0xff, 0x83, 0x45, 0xe6, 0x92, 0xc0, 0xf8, 0x6e,
+    0x5b, 0x48, 0xe0, 0x1b, 0x99, 0x6c, 0xad, 0xc0, 0x01, 0x62, 0x2f, 0xb5, 0xe3, 0x63, 0xb4, 0x21,
+]);
+
+/// Keccak hash of empty code, used as code hash of accounts without code
+pub const EMPTY_CODE_HASH: H256 = H256([
+    0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7, 0x03, 0xc0,
+    0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85, 0xa4, 0x70,
+]);
+
+/// Verifies the account proof and all contained storage proofs against `state_root`
+pub fn verify_proof(state_root: H256, proof: &AccountProof) -> Result<(), ProofError> {
+    verify_account_proof(state_root, proof)?;
+    proof
+        .storage_proof
+        .iter()
+        .try_for_each(|storage_proof| verify_storage_proof(proof.storage_hash, storage_proof))
+}
+
+/// Verifies that the account fields of `proof` are contained in the state trie with `state_root`
+///
+/// Accounts which do not exist must be proven absent and have the fields of an empty account.
+pub fn verify_account_proof(state_root: H256, proof: &AccountProof) -> Result<(), ProofError> {
+    let key = keccak256(proof.address.as_bytes());
+    let is_empty = proof.nonce.is_zero()
+        && proof.balance.is_zero()
+        && proof.storage_hash == EMPTY_TRIE_ROOT
+        && proof.code_hash == EMPTY_CODE_HASH;
+
+    match walk_trie(state_root, &key, &proof.account_proof)? {
+        Some(value) => {
+            let mut expected = RlpStream::new_list(4);
+            expected
+                .append(&proof.nonce)
+                .append(&proof.balance)
+                .append(&proof.storage_hash)
+                .append(&proof.code_hash);
+            if value == expected.out().to_vec() {
+                Ok(())
+            } else {
+                Err(ProofError::ValueMismatch)
+            }
+        }
+        None if is_empty => Ok(()),
+        None => Err(ProofError::ValueMismatch),
+    }
+}
+
+/// Verifies that the storage slot of `proof` is contained in the storage trie with `storage_hash`
+///
+/// Slots with a value of zero are not stored and must be proven absent.
+pub fn verify_storage_proof(storage_hash: H256, proof: &StorageProof) -> Result<(), ProofError> {
+    let key = keccak256(H256::from_uint(&proof.key).as_bytes());
+    match walk_trie(storage_hash, &key, &proof.proof)? {
+        Some(value) if Rlp::new(&value).as_val::<U256>()? == proof.value => Ok(()),
+        None if proof.value.is_zero() => Ok(()),
+        _ => Err(ProofError::ValueMismatch),
+    }
+}
+
+/// Verifies that `code`, e.g. returned by `eth_getCode`, matches the code hash of a verified proof
+pub fn verify_code(proof: &AccountProof, code: &Bytes) -> Result<(), ProofError> {
+    if H256(keccak256(&code.0)) == proof.code_hash {
+        Ok(())
+    } else {
+        Err(ProofError::CodeMismatch)
+    }
+}
+
+// Follows `key` from the root through the proof nodes. Returns the value stored under `key` or
+// `None` if the proof shows that `key` is not part of the trie.
+fn walk_trie(root: H256, key: &[u8; 32], proof: &[Bytes]) -> Result<Option<Vec<u8>>, ProofError> {
+    let nibbles = key
+        .iter()
+        .flat_map(|byte| vec![byte >> 4, byte & 0x0f])
+        .collect::<Vec<u8>>();
+    // Nodes return no proof nodes at all for empty tries, like the storage of an EOA
+    if proof.is_empty() && root == EMPTY_TRIE_ROOT {
+        return Ok(None);
+    }
+    let mut nodes = proof.iter();
+    let mut position = 0;
+
+    let first = nodes.next().ok_or(ProofError::IncompleteProof)?;
+    if H256(keccak256(&first.0)) != root {
+        return Err(ProofError::HashMismatch(0));
+    }
+    let mut node = first.0.clone();
+    let mut index = 0;
+
+    let result = loop {
+        let rlp = Rlp::new(&node);
+        let child = match rlp.item_count()? {
+            17 => {
+                if position == nibbles.len() {
+                    let value = rlp.at(16)?;
+                    break (!value.is_empty()).then(|| value.data().map(<[u8]>::to_vec));
+                }
+                let child = rlp.at(nibbles[position] as usize)?;
+                position += 1;
+                child
+            }
+            2 => {
+                let (is_leaf, path) = decode_path(rlp.at(0)?.data()?)?;
+                let remaining = &nibbles[position..];
+                if is_leaf {
+                    break (remaining == path.as_slice())
+                        .then(|| rlp.at(1)?.data().map(<[u8]>::to_vec));
+                }
+                if !remaining.starts_with(&path) {
+                    break None;
+                }
+                position += path.len();
+                rlp.at(1)?
+            }
+            _ => return Err(ProofError::Rlp(DecoderError::RlpIncorrectListLen)),
+        };
+
+        if child.is_empty() {
+            break None;
+        } else if child.is_list() {
+            // Nodes smaller than 32 bytes are embedded into their parent
+            node = child.as_raw().to_vec();
+        } else {
+            index += 1;
+            let next = nodes.next().ok_or(ProofError::IncompleteProof)?;
+            if keccak256(&next.0) != child.data()? {
+                return Err(ProofError::HashMismatch(index));
+            }
+            node = next.0.clone();
+        }
+    };
+
+    if nodes.next().is_some() {
+        return Err(ProofError::UnusedNodes);
+    }
+    result.transpose().map_err(ProofError::from)
+}
+
+// Decodes the hex prefix encoding of leaf and extension paths
+fn decode_path(encoded: &[u8]) -> Result<(bool, Vec<u8>), ProofError> {
+    let first = *encoded.first().ok_or(ProofError::InvalidPath)?;
+    let flag = first >> 4;
+    if flag > 3 {
+        return Err(ProofError::InvalidPath);
+    }
+
+    let mut path = Vec::with_capacity(encoded.len() * 2);
+    if flag & 1 == 1 {
+        path.push(first & 0x0f);
+    }
+    for byte in &encoded[1..] {
+        path.push(byte >> 4);
+        path.push(byte & 0x0f);
+    }
+    Ok((flag & 2 == 2, path))
+}
+
+/// An error type collecting what can go wrong when verifying proofs
+#[derive(Debug, Error)]
+pub enum ProofError {
+    #[error("Proof Error: Hash of proof node {0} does not match its reference")]
+    HashMismatch(usize),
+    #[error("Proof Error: Proven value does not match the returned value")]
+    ValueMismatch,
+    #[error("Proof Error: Code does not match the code hash")]
+    CodeMismatch,
+    #[error("Proof Error: Proof ends before reaching the key")]
+    IncompleteProof,
+    #[error("Proof Error: Proof contains unused nodes")]
+    UnusedNodes,
+    #[error("Proof Error: Invalid path encoding")]
+    InvalidPath,
+    #[error("Proof Error: Invalid RLP: {0}")]
+    Rlp(#[from] DecoderError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::H160;
+    use std::str::FromStr;
+
+    fn leaf(path: &[u8], value: &[u8]) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&encode_path(path, true)).append(&value);
+        stream.out().to_vec()
+    }
+
+    fn encode_path(path: &[u8], is_leaf: bool) -> Vec<u8> {
+        let flag = if is_leaf { 2 } else { 0 };
+        let mut encoded = if path.len() % 2 == 1 {
+            vec![((flag + 1) << 4) | path[0]]
+        } else {
+            vec![flag << 4]
+        };
+        let even = if path.len() % 2 == 1 {
+            &path[1..]
+        } else {
+            path
+        };
+        encoded.extend(even.chunks(2).map(|pair| (pair[0] << 4) | pair[1]));
+        encoded
+    }
+
+    fn branch(children: &[(u8, &[u8])]) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(17);
+        for nibble in 0..16 {
+            match children.iter().find(|(index, _)| *index == nibble) {
+                Some((_, child)) => stream.append(&keccak256(child).to_vec()),
+                None => stream.append_empty_data(),
+            };
+        }
+        stream.append_empty_data();
+        stream.out().to_vec()
+    }
+
+    fn nibbles(key: &[u8; 32]) -> Vec<u8> {
+        key.iter()
+            .flat_map(|byte| vec![byte >> 4, byte & 0x0f])
+            .collect()
+    }
+
+    fn account_rlp(proof: &AccountProof) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(4);
+        stream
+            .append(&proof.nonce)
+            .append(&proof.balance)
+            .append(&proof.storage_hash)
+            .append(&proof.code_hash);
+        stream.out().to_vec()
+    }
+
+    // Builds a state trie with a branch as root and one account leaf below it
+    fn account_fixture() -> (H256, AccountProof) {
+        let mut proof = AccountProof {
+            address: H160::from_str("0xDc677f7C5060B0b441d30F361D0c8529Ac04E099").unwrap(),
+            account_proof: vec![],
+            balance: U256::exp10(20),
+            code_hash: EMPTY_CODE_HASH,
+            nonce: U256::from(3),
+            storage_hash: EMPTY_TRIE_ROOT,
+            storage_proof: vec![],
+        };
+        let key = nibbles(&keccak256(proof.address.as_bytes()));
+        let account_leaf = leaf(&key[1..], &account_rlp(&proof));
+        let root = branch(&[(key[0], &account_leaf)]);
+        proof.account_proof = vec![Bytes(root.clone()), Bytes(account_leaf)];
+        (H256(keccak256(&root)), proof)
+    }
+
+    #[test]
+    fn test_proof_account_inclusion() {
+        let (state_root, proof) = account_fixture();
+        assert!(verify_account_proof(state_root, &proof).is_ok());
+        assert!(verify_proof(state_root, &proof).is_ok());
+    }
+
+    #[test]
+    fn test_proof_account_tampered() {
+        let (state_root, proof) = account_fixture();
+
+        let mut wrong_balance = proof.clone();
+        wrong_balance.balance = U256::exp10(21);
+        assert!(matches!(
+            verify_account_proof(state_root, &wrong_balance),
+            Err(ProofError::ValueMismatch)
+        ));
+
+        let mut wrong_node = proof.clone();
+        wrong_node.account_proof[1].0[5] ^= 1;
+        assert!(matches!(
+            verify_account_proof(state_root, &wrong_node),
+            Err(ProofError::HashMismatch(1))
+        ));
+
+        assert!(matches!(
+            verify_account_proof(H256::zero(), &proof),
+            Err(ProofError::HashMismatch(0))
+        ));
+
+        let mut incomplete = proof;
+        incomplete.account_proof.pop();
+        assert!(matches!(
+            verify_account_proof(state_root, &incomplete),
+            Err(ProofError::IncompleteProof)
+        ));
+    }
+
+    #[test]
+    fn test_proof_account_absence() {
+        let (state_root, proof) = account_fixture();
+        // Find an address whose hashed key ends in an empty slot of the root branch
+        let existing = keccak256(proof.address.as_bytes())[0] >> 4;
+        let absent_address = (0..u64::MAX)
+            .map(H160::from_low_u64_be)
+            .find(|address| keccak256(address.as_bytes())[0] >> 4 != existing)
+            .unwrap();
+        let mut absent = AccountProof {
+            address: absent_address,
+            account_proof: vec![proof.account_proof[0].clone()],
+            balance: U256::zero(),
+            code_hash: EMPTY_CODE_HASH,
+            nonce: U256::zero(),
+            storage_hash: EMPTY_TRIE_ROOT,
+            storage_proof: vec![],
+        };
+        assert!(verify_account_proof(state_root, &absent).is_ok());
+
+        absent.balance = U256::one();
+        assert!(matches!(
+            verify_account_proof(state_root, &absent),
+            Err(ProofError::ValueMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_proof_storage() {
+        let slot = U256::from(2);
+        let key = nibbles(&keccak256(H256::from_uint(&slot).as_bytes()));
+        let value = rlp::encode(&U256::from(42)).to_vec();
+        let storage_leaf = leaf(&key, &value);
+        let storage_hash = H256(keccak256(&storage_leaf));
+        let mut proof = StorageProof {
+            key: slot,
+            value: U256::from(42),
+            proof: vec![Bytes(storage_leaf)],
+        };
+        assert!(verify_storage_proof(storage_hash, &proof).is_ok());
+
+        proof.value = U256::from(43);
+        assert!(matches!(
+            verify_storage_proof(storage_hash, &proof),
+            Err(ProofError::ValueMismatch)
+        ));
+
+        // A leaf with a different path proves that slot 3 is empty
+        proof.key = U256::from(3);
+        proof.value = U256::zero();
+        assert!(verify_storage_proof(storage_hash, &proof).is_ok());
+    }
+
+    #[test]
+    fn test_proof_storage_of_eoa() {
+        let (state_root, mut proof) = account_fixture();
+        proof.storage_proof = vec![StorageProof {
+            key: U256::zero(),
+            value: U256::zero(),
+            proof: vec![],
+        }];
+        assert!(verify_proof(state_root, &proof).is_ok());
+
+        proof.storage_proof[0].value = U256::one();
+        assert!(matches!(
+            verify_proof(state_root, &proof),
+            Err(ProofError::ValueMismatch)
+        ));
+
+        // Only the empty trie may come without proof nodes
+        proof.storage_proof[0].value = U256::zero();
+        assert!(matches!(
+            verify_storage_proof(H256::zero(), &proof.storage_proof[0]),
+            Err(ProofError::IncompleteProof)
+        ));
+    }
+
+    #[test]
+    fn test_proof_code() {
+        let (_, proof) = account_fixture();
+        assert!(verify_code(&proof, &Bytes(vec![])).is_ok());
+        assert!(matches!(
+            verify_code(&proof, &Bytes(vec![0x60])),
+            Err(ProofError::CodeMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_proof_decode_path() {
+        assert_eq!(decode_path(&[0x20, 0x12]).unwrap(), (true, vec![1, 2]));
+        assert_eq!(decode_path(&[0x31, 0x23]).unwrap(), (true, vec![1, 2, 3]));
+        assert_eq!(decode_path(&[0x00, 0x12]).unwrap(), (false, vec![1, 2]));
+        assert_eq!(decode_path(&[0x1a]).unwrap(), (false, vec![0xa]));
+        assert!(decode_path(&[0x40]).is_err());
+    }
+}
@@ -0,0 +1,66 @@
+use super::Rpc;
+use crate::types::{NodeInfo, PeerInfo};
+
+pub fn admin_node_info() -> Rpc<NodeInfo> {
+    Rpc::new("admin_nodeInfo")
+}
+
+pub fn admin_peers() -> Rpc<Vec<PeerInfo>> {
+    Rpc::new("admin_peers")
+}
+
+pub fn admin_add_peer(enode: String) -> Rpc<bool> {
+    let mut rpc = Rpc::new("admin_addPeer");
+    rpc.add_param(enode);
+    rpc
+}
+
+pub fn admin_remove_peer(enode: String) -> Rpc<bool> {
+    let mut rpc = Rpc::new("admin_removePeer");
+    rpc.add_param(enode);
+    rpc
+}
+
+/// Starts the http server. Unset parameters fall back to the defaults of the node.
+/// `apis` is a comma separated list of namespaces, e.g. `"eth,net,web3"`.
+pub fn admin_start_http(
+    host: Option<String>,
+    port: Option<u16>,
+    cors: Option<String>,
+    apis: Option<String>,
+) -> Rpc<bool> {
+    let mut rpc = Rpc::new("admin_startHTTP");
+    rpc.add_param(host);
+    rpc.add_param(port);
+    rpc.add_param(cors);
+    rpc.add_param(apis);
+    rpc
+}
+
+pub fn admin_stop_http() -> Rpc<bool> {
+    Rpc::new("admin_stopHTTP")
+}
+
+/// Starts the websocket server. Unset parameters fall back to the defaults of the node.
+/// `apis` is a comma separated list of namespaces, e.g. `"eth,net,web3"`.
+pub fn admin_start_ws(
+    host: Option<String>,
+    port: Option<u16>,
+    allowed_origins: Option<String>,
+    apis: Option<String>,
+) -> Rpc<bool> {
+    let mut rpc = Rpc::new("admin_startWS");
+    rpc.add_param(host);
+    rpc.add_param(port);
+    rpc.add_param(allowed_origins);
+    rpc.add_param(apis);
+    rpc
+}
+
+pub fn admin_stop_ws() -> Rpc<bool> {
+    Rpc::new("admin_stopWS")
+}
+
+pub fn admin_datadir() -> Rpc<String> {
+    Rpc::new("admin_datadir")
+}
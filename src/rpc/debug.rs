@@ -0,0 +1,54 @@
+use super::Rpc;
+use crate::types::{BlockParameter, BlockTrace, Call, TraceOptions, TraceResult, H256};
+
+pub fn debug_trace_transaction(
+    transaction_hash: H256,
+    options: Option<TraceOptions>,
+) -> Rpc<TraceResult> {
+    let mut rpc = Rpc::new("debug_traceTransaction");
+    rpc.add_param(transaction_hash);
+    if let Some(options) = options {
+        rpc.add_param(options);
+    }
+    rpc
+}
+
+pub fn debug_trace_call(
+    call: Call,
+    block_param: Option<BlockParameter>,
+    options: Option<TraceOptions>,
+) -> Rpc<TraceResult> {
+    let block_param = block_param.unwrap_or(BlockParameter::Latest);
+    let mut rpc = Rpc::new("debug_traceCall");
+    rpc.add_param(call);
+    rpc.add_param(block_param);
+    if let Some(options) = options {
+        rpc.add_param(options);
+    }
+    rpc
+}
+
+pub fn debug_trace_block_by_number(
+    block_param: Option<BlockParameter>,
+    options: Option<TraceOptions>,
+) -> Rpc<Vec<BlockTrace>> {
+    let block_param = block_param.unwrap_or(BlockParameter::Latest);
+    let mut rpc = Rpc::new("debug_traceBlockByNumber");
+    rpc.add_param(block_param);
+    if let Some(options) = options {
+        rpc.add_param(options);
+    }
+    rpc
+}
+
+pub fn debug_trace_block_by_hash(
+    block_hash: H256,
+    options: Option<TraceOptions>,
+) -> Rpc<Vec<BlockTrace>> {
+    let mut rpc = Rpc::new("debug_traceBlockByHash");
+    rpc.add_param(block_hash);
+    if let Some(options) = options {
+        rpc.add_param(options);
+    }
+    rpc
+}
@@ -0,0 +1,54 @@
+//! Functions of the authenticated Engine API
+//!
+//! The Engine API is served on a separate port, usually 8551, and requires a connector with
+//! [JWT credentials](crate::Credentials::Jwt).
+
+use super::Rpc;
+use crate::types::{
+    ExecutionPayload, ExecutionPayloadEnvelope, ForkchoiceState, ForkchoiceUpdated,
+    PayloadAttributes, PayloadStatus, H64,
+};
+
+pub fn engine_new_payload_v1(payload: ExecutionPayload) -> Rpc<PayloadStatus> {
+    let mut rpc = Rpc::new("engine_newPayloadV1");
+    rpc.add_param(payload);
+    rpc
+}
+
+pub fn engine_new_payload_v2(payload: ExecutionPayload) -> Rpc<PayloadStatus> {
+    let mut rpc = Rpc::new("engine_newPayloadV2");
+    rpc.add_param(payload);
+    rpc
+}
+
+pub fn engine_forkchoice_updated_v1(
+    forkchoice_state: ForkchoiceState,
+    payload_attributes: Option<PayloadAttributes>,
+) -> Rpc<ForkchoiceUpdated> {
+    let mut rpc = Rpc::new("engine_forkchoiceUpdatedV1");
+    rpc.add_param(forkchoice_state);
+    rpc.add_param(payload_attributes);
+    rpc
+}
+
+pub fn engine_forkchoice_updated_v2(
+    forkchoice_state: ForkchoiceState,
+    payload_attributes: Option<PayloadAttributes>,
+) -> Rpc<ForkchoiceUpdated> {
+    let mut rpc = Rpc::new("engine_forkchoiceUpdatedV2");
+    rpc.add_param(forkchoice_state);
+    rpc.add_param(payload_attributes);
+    rpc
+}
+
+pub fn engine_get_payload_v1(payload_id: H64) -> Rpc<ExecutionPayload> {
+    let mut rpc = Rpc::new("engine_getPayloadV1");
+    rpc.add_param(payload_id);
+    rpc
+}
+
+pub fn engine_get_payload_v2(payload_id: H64) -> Rpc<ExecutionPayloadEnvelope> {
+    let mut rpc = Rpc::new("engine_getPayloadV2");
+    rpc.add_param(payload_id);
+    rpc
+}
@@ -1,8 +1,10 @@
 use super::Rpc;
 use crate::types::{
-    Block, BlockParameter, Bytes, Call, Filter, GasCall, HashOrLog, SignedTransaction, SyncInfo,
-    Transaction, TransactionReceipt, TransactionRequest, H160, H256, H64, U128, U256, U64,
+    AccessListResult, AccountProof, Block, BlockId, BlockOverrides, BlockParameter, Bytes, Call,
+    Filter, GasCall, HashOrLog, SignedTransaction, StateOverride, SyncInfo, Transaction,
+    TransactionReceipt, TransactionRequest, TypedData, H160, H256, H64, U128, U256, U64,
 };
+use ethereum_types::BigEndianHash;
 
 pub fn eth_protocol_version() -> Rpc<String> {
     Rpc::new("eth_protocolVersion")
@@ -28,6 +30,10 @@ pub fn eth_gas_price() -> Rpc<U256> {
     Rpc::new("eth_gasPrice")
 }
 
+pub fn eth_blob_base_fee() -> Rpc<U256> {
+    Rpc::new("eth_blobBaseFee")
+}
+
 pub fn eth_accounts() -> Rpc<Vec<H160>> {
     Rpc::new("eth_accounts")
 }
@@ -36,11 +42,11 @@ pub fn eth_block_number() -> Rpc<U64> {
     Rpc::new("eth_blockNumber")
 }
 
-pub fn eth_get_balance(address: H160, block_param: Option<BlockParameter>) -> Rpc<U256> {
-    let block_param = block_param.unwrap_or(BlockParameter::Latest);
+pub fn eth_get_balance(address: H160, block_id: Option<BlockId>) -> Rpc<U256> {
+    let block_id = block_id.unwrap_or_default();
     let mut rpc = Rpc::new("eth_getBalance");
     rpc.add_param(address);
-    rpc.add_param(block_param);
+    rpc.add_param(block_id);
     rpc
 }
 
@@ -65,21 +71,40 @@ pub fn eth_get_transaction_receipt(transaction_hash: H256) -> Rpc<Option<Transac
 pub fn eth_get_storage_at(
     address: H160,
     storage_pos: U256,
-    block_param: Option<BlockParameter>,
+    block_id: Option<BlockId>,
 ) -> Rpc<Bytes> {
-    let block_param = block_param.unwrap_or(BlockParameter::Latest);
+    let block_id = block_id.unwrap_or_default();
     let mut rpc = Rpc::new("eth_getStorageAt");
     rpc.add_param(address);
     rpc.add_param(storage_pos);
-    rpc.add_param(block_param);
+    rpc.add_param(block_id);
     rpc
 }
 
-pub fn eth_get_transaction_count(address: H160, block_param: Option<BlockParameter>) -> Rpc<U256> {
-    let block_param = block_param.unwrap_or(BlockParameter::Latest);
+/// Storage keys are the same positions as used by [eth_get_storage_at]
+pub fn eth_get_proof(
+    address: H160,
+    storage_keys: Vec<U256>,
+    block_id: Option<BlockId>,
+) -> Rpc<AccountProof> {
+    let block_id = block_id.unwrap_or_default();
+    // Some nodes only accept storage keys with a length of 32 bytes
+    let storage_keys = storage_keys
+        .into_iter()
+        .map(|key| H256::from_uint(&key))
+        .collect::<Vec<H256>>();
+    let mut rpc = Rpc::new("eth_getProof");
+    rpc.add_param(address);
+    rpc.add_param(storage_keys);
+    rpc.add_param(block_id);
+    rpc
+}
+
+pub fn eth_get_transaction_count(address: H160, block_id: Option<BlockId>) -> Rpc<U256> {
+    let block_id = block_id.unwrap_or_default();
     let mut rpc = Rpc::new("eth_getTransactionCount");
     rpc.add_param(address);
-    rpc.add_param(block_param);
+    rpc.add_param(block_id);
     rpc
 }
 
@@ -120,11 +145,11 @@ pub fn eth_get_uncle_count_by_block_number(block_param: Option<BlockParameter>)
     rpc
 }
 
-pub fn eth_get_code(address: H160, block_param: Option<BlockParameter>) -> Rpc<Bytes> {
-    let block_param = block_param.unwrap_or(BlockParameter::Latest);
+pub fn eth_get_code(address: H160, block_id: Option<BlockId>) -> Rpc<Bytes> {
+    let block_id = block_id.unwrap_or_default();
     let mut rpc = Rpc::new("eth_getCode");
     rpc.add_param(address);
-    rpc.add_param(block_param);
+    rpc.add_param(block_id);
     rpc
 }
 
@@ -135,6 +160,14 @@ pub fn eth_sign(address: H160, data: Bytes) -> Rpc<Bytes> {
     rpc
 }
 
+/// Signs structured data as defined in EIP-712 with an unlocked account
+pub fn eth_sign_typed_data_v4(address: H160, typed_data: TypedData) -> Rpc<Bytes> {
+    let mut rpc = Rpc::new("eth_signTypedData_v4");
+    rpc.add_param(address);
+    rpc.add_param(typed_data);
+    rpc
+}
+
 // DEVIATION FROM SPEC
 // c.f. https://github.com/ethereum/go-ethereum/issues/22223
 // also geth returns something like: {raw: hex_encoded_tx, tx: json_encoded_tx}, however according to JSON RPC
@@ -153,19 +186,54 @@ pub fn eth_send_raw_transaction(raw_transaction: Bytes) -> Rpc<H256> {
     rpc
 }
 
-pub fn eth_call(call: Call, block_param: Option<BlockParameter>) -> Rpc<Bytes> {
-    let block_param = block_param.unwrap_or(BlockParameter::Latest);
+/// Executes a call without creating a transaction
+///
+/// The call can be simulated against hypothetical state by overriding accounts with
+/// `state_override` and block fields with `block_overrides`.
+pub fn eth_call(
+    call: Call,
+    block_id: Option<BlockId>,
+    state_override: Option<StateOverride>,
+    block_overrides: Option<BlockOverrides>,
+) -> Rpc<Bytes> {
+    let block_id = block_id.unwrap_or_default();
     let mut rpc = Rpc::new("eth_call");
     rpc.add_param(call);
-    rpc.add_param(block_param);
+    rpc.add_param(block_id);
+    if state_override.is_some() || block_overrides.is_some() {
+        rpc.add_param(state_override.unwrap_or_default());
+    }
+    if let Some(block_overrides) = block_overrides {
+        rpc.add_param(block_overrides);
+    }
     rpc
 }
 
-pub fn eth_estimate_gas(gas_call: GasCall, block_param: Option<BlockParameter>) -> Rpc<U256> {
+pub fn eth_estimate_gas(
+    gas_call: GasCall,
+    block_param: Option<BlockParameter>,
+    state_override: Option<StateOverride>,
+) -> Rpc<U256> {
     let block_param = block_param.unwrap_or(BlockParameter::Latest);
     let mut rpc = Rpc::new("eth_estimateGas");
     rpc.add_param(gas_call);
     rpc.add_param(block_param);
+    if let Some(state_override) = state_override {
+        rpc.add_param(state_override);
+    }
+    rpc
+}
+
+/// Creates an access list for a transaction, which can be attached with
+/// [TransactionRequest::with_access_list]
+pub fn eth_create_access_list(
+    transaction: TransactionRequest,
+    block_param: Option<BlockParameter>,
+) -> Rpc<AccessListResult> {
+    let block_param = block_param.unwrap_or(BlockParameter::Latest);
+    let mut rpc = Rpc::new("eth_createAccessList");
+    rpc.add_param(transaction);
+    rpc.add_param(block_param);
     rpc
 }
 
@@ -0,0 +1,33 @@
+use super::Rpc;
+use crate::types::{H160, U256};
+
+pub fn miner_start(threads: Option<u32>) -> Rpc<()> {
+    let mut rpc = Rpc::new("miner_start");
+    if let Some(threads) = threads {
+        rpc.add_param(threads);
+    }
+    rpc
+}
+
+pub fn miner_stop() -> Rpc<()> {
+    Rpc::new("miner_stop")
+}
+
+pub fn miner_set_etherbase(address: H160) -> Rpc<bool> {
+    let mut rpc = Rpc::new("miner_setEtherbase");
+    rpc.add_param(address);
+    rpc
+}
+
+pub fn miner_set_gas_price(gas_price: U256) -> Rpc<bool> {
+    let mut rpc = Rpc::new("miner_setGasPrice");
+    rpc.add_param(gas_price);
+    rpc
+}
+
+/// Sets the extra data of mined blocks. Geth expects a plain string and not hex.
+pub fn miner_set_extra(extra: String) -> Rpc<bool> {
+    let mut rpc = Rpc::new("miner_setExtra");
+    rpc.add_param(extra);
+    rpc
+}
@@ -6,6 +6,13 @@
 //!     - [real-time events](https://geth.ethereum.org/docs/rpc/pubsub)
 //!     - [personal](https://geth.ethereum.org/docs/rpc/ns-personal)
 //!     - [txpool](https://geth.ethereum.org/docs/rpc/ns-txpool)
+//!     - [debug](https://geth.ethereum.org/docs/rpc/ns-debug) (tracing only)
+//!     - [admin](https://geth.ethereum.org/docs/rpc/ns-admin)
+//!     - [miner](https://geth.ethereum.org/docs/rpc/ns-miner)
+//! - the authenticated [Engine API](https://github.com/ethereum/execution-apis/tree/main/src/engine)
+//!   in the [engine] module
+//! - the [OpenEthereum trace module](https://openethereum.github.io/JSONRPC-trace-module),
+//!   which is also offered by Erigon and Nethermind
 //!
 //! There are some deviations between what is really supported and the official specification.
 //! This is why some functions are marked as deprecated. They will probably be removed.
@@ -20,17 +27,26 @@ use serde_json::Value;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 
+pub use admin::*;
+pub use debug::*;
 pub use eth::*;
+pub use miner::*;
 pub use net::*;
 pub use personal::*;
 pub(crate) use sub::eth_unsubscribe;
+pub use trace::*;
 pub use txpool::*;
 pub use web3::*;
 
+mod admin;
+mod debug;
+pub mod engine;
 mod eth;
+mod miner;
 mod net;
 mod personal;
 pub mod sub;
+mod trace;
 mod txpool;
 mod web3;
 
@@ -1,7 +1,9 @@
 //! Provides functions used for subscriptions
 
 use super::Rpc;
-use crate::types::{BlockHeader, FilterSubscription, Log, SyncInfoSubscription, H256, U128};
+use crate::types::{
+    BlockHeader, FilterSubscription, Log, SyncInfoSubscription, Transaction, H256, U128,
+};
 use std::marker::PhantomData;
 
 /// Rpc to start a subscription
@@ -28,6 +30,18 @@ pub fn eth_subscribe_new_pending_transactions() -> SubscriptionRequest<H256> {
     }
 }
 
+/// Yields full transaction objects instead of hashes. Needs a node which supports
+/// the `fullTx` flag, e.g. geth >= 1.11.
+pub fn eth_subscribe_new_pending_transactions_full() -> SubscriptionRequest<Transaction> {
+    let mut rpc = Rpc::new("eth_subscribe");
+    rpc.add_param("newPendingTransactions");
+    rpc.add_param(true);
+    SubscriptionRequest {
+        rpc,
+        result_type: PhantomData,
+    }
+}
+
 pub fn eth_subscribe_syncing() -> SubscriptionRequest<SyncInfoSubscription> {
     let mut rpc = Rpc::new("eth_subscribe");
     rpc.add_param("syncing");
@@ -0,0 +1,46 @@
+use super::Rpc;
+use crate::types::{
+    BlockParameter, Call, LocalizedTrace, TraceFilter, TraceResults, TraceType, H256,
+};
+
+pub fn trace_block(block_param: Option<BlockParameter>) -> Rpc<Vec<LocalizedTrace>> {
+    let block_param = block_param.unwrap_or(BlockParameter::Latest);
+    let mut rpc = Rpc::new("trace_block");
+    rpc.add_param(block_param);
+    rpc
+}
+
+pub fn trace_transaction(transaction_hash: H256) -> Rpc<Vec<LocalizedTrace>> {
+    let mut rpc = Rpc::new("trace_transaction");
+    rpc.add_param(transaction_hash);
+    rpc
+}
+
+pub fn trace_filter(filter: TraceFilter) -> Rpc<Vec<LocalizedTrace>> {
+    let mut rpc = Rpc::new("trace_filter");
+    rpc.add_param(filter);
+    rpc
+}
+
+pub fn trace_call(
+    call: Call,
+    trace_types: Vec<TraceType>,
+    block_param: Option<BlockParameter>,
+) -> Rpc<TraceResults> {
+    let block_param = block_param.unwrap_or(BlockParameter::Latest);
+    let mut rpc = Rpc::new("trace_call");
+    rpc.add_param(call);
+    rpc.add_param(trace_types);
+    rpc.add_param(block_param);
+    rpc
+}
+
+pub fn trace_replay_transaction(
+    transaction_hash: H256,
+    trace_types: Vec<TraceType>,
+) -> Rpc<TraceResults> {
+    let mut rpc = Rpc::new("trace_replayTransaction");
+    rpc.add_param(transaction_hash);
+    rpc.add_param(trace_types);
+    rpc
+}
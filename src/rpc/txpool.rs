@@ -1,5 +1,5 @@
 use super::Rpc;
-use crate::types::{TxPoolContent, TxPoolInspect, TxPoolStatus};
+use crate::types::{TxPoolContent, TxPoolContentFrom, TxPoolInspect, TxPoolStatus, H160};
 
 pub fn txpool_status() -> Rpc<TxPoolStatus> {
     Rpc::new("txpool_status")
@@ -12,3 +12,9 @@ pub fn txpool_content() -> Rpc<TxPoolContent> {
 pub fn txpool_inspect() -> Rpc<TxPoolInspect> {
     Rpc::new("txpool_inspect")
 }
+
+pub fn txpool_content_from(address: H160) -> Rpc<TxPoolContentFrom> {
+    let mut rpc = Rpc::new("txpool_contentFrom");
+    rpc.add_param(address);
+    rpc
+}
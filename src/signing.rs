@@ -0,0 +1,140 @@
+//! Local signing and recovery of messages as defined in [EIP-191](https://eips.ethereum.org/EIPS/eip-191)
+//!
+//! These work like [personal_sign](crate::rpc::personal_sign) and
+//! [personal_ec_recover](crate::rpc::personal_ec_recover), but without a node and without
+//! unlocking an account. Messages are prefixed with `"\x19Ethereum Signed Message:\n"` and their
+//! length before hashing.
+
+use crate::types::{PrivateKey, Signature, SignatureError, H160, H256};
+use crate::utils::keccak256;
+use libsecp256k1::{Message, PublicKey, RecoveryId, SecretKey};
+use thiserror::Error;
+
+/// Hashes a message with the EIP-191 prefix, which is what gets signed by `personal_sign`
+pub fn hash_message(message: &[u8]) -> H256 {
+    let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    prefixed.extend_from_slice(message);
+    H256::from(keccak256(&prefixed))
+}
+
+/// Signs a message with the EIP-191 prefix
+pub fn sign_message(message: &[u8], private_key: &PrivateKey) -> Result<Signature, SigningError> {
+    sign_hash(hash_message(message), private_key)
+}
+
+/// Recovers the address which signed a message with the EIP-191 prefix
+pub fn recover_message(message: &[u8], signature: &Signature) -> Result<H160, SigningError> {
+    recover_hash(hash_message(message), signature)
+}
+
+/// Signs a hash directly, returning a signature with `v` of 27 or 28
+pub fn sign_hash(hash: H256, private_key: &PrivateKey) -> Result<Signature, SigningError> {
+    let secret_key = secret_key(private_key)?;
+    let (signature, recovery_id) = libsecp256k1::sign(&Message::parse(&hash.0), &secret_key);
+    let signature = signature.serialize();
+    Ok(Signature {
+        r: H256::from_slice(&signature[..32]),
+        s: H256::from_slice(&signature[32..]),
+        v: 27 + recovery_id.serialize() as u64,
+    })
+}
+
+/// Recovers the address which signed a hash
+pub fn recover_hash(hash: H256, signature: &Signature) -> Result<H160, SigningError> {
+    let mut rs = [0u8; 64];
+    rs[..32].copy_from_slice(signature.r.as_bytes());
+    rs[32..].copy_from_slice(signature.s.as_bytes());
+    let recovery_id = RecoveryId::parse(signature.recovery_id()?)?;
+    let public_key = libsecp256k1::recover(
+        &Message::parse(&hash.0),
+        &libsecp256k1::Signature::parse_standard(&rs)?,
+        &recovery_id,
+    )?;
+    Ok(public_key_address(&public_key))
+}
+
+/// Derives the address belonging to a private key
+pub fn address(private_key: &PrivateKey) -> Result<H160, SigningError> {
+    let public_key = PublicKey::from_secret_key(&secret_key(private_key)?);
+    Ok(public_key_address(&public_key))
+}
+
+fn secret_key(private_key: &PrivateKey) -> Result<SecretKey, SigningError> {
+    let key = match private_key {
+        PrivateKey::ZeroXPrefixed(key) | PrivateKey::NonPrefixed(key) => key,
+    };
+    Ok(SecretKey::parse(&key.0)?)
+}
+
+// The address consists of the last 20 bytes of the hashed uncompressed public key
+fn public_key_address(public_key: &PublicKey) -> H160 {
+    let hash = keccak256(&public_key.serialize()[1..]);
+    H160::from_slice(&hash[12..])
+}
+
+/// An error type collecting what can go wrong when signing or recovering
+#[derive(Debug, Error)]
+pub enum SigningError {
+    #[error("Signing Error: {0}")]
+    Signature(#[from] SignatureError),
+    #[error("Signing Error: {0}")]
+    Secp256k1(#[from] libsecp256k1::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Bytes;
+    use std::convert::TryFrom;
+    use std::str::FromStr;
+
+    // Same key and address as the personal namespace tests
+    const SECRET: &str = "fdc861959d1768d936bf17eec56260d4de3a7473e58c349e31beba539e5fc88d";
+    const ADDRESS: &str = "0xdc677f7c5060b0b441d30f361d0c8529ac04e099";
+
+    fn private_key() -> PrivateKey {
+        PrivateKey::NonPrefixed(H256::from_str(SECRET).unwrap())
+    }
+
+    #[test]
+    fn test_signing_hash_message() {
+        assert_eq!(
+            hash_message(b"Hello World"),
+            H256::from_str("0xa1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_signing_matches_node() {
+        // Signature returned by personal_sign of geth
+        let expected = Bytes::from_str(concat!(
+            "0x67e4a4cf3b8cfb7d9a568482e9b6deb6350bc7701ae0448b92752b463e7dc97",
+            "c09c424607fbcf1cb4f6ec1c6a6c60a3527dcfe11412a3bff26218ca9f0bdef9d1b",
+        ))
+        .unwrap();
+        let signature = sign_message(b"checkmate", &private_key()).unwrap();
+        assert_eq!(signature.to_bytes().unwrap(), expected);
+        assert_eq!(
+            recover_message(b"checkmate", &Signature::try_from(&expected).unwrap()).unwrap(),
+            H160::from_str(ADDRESS).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_signing_sign_and_recover() {
+        let address = H160::from_str(ADDRESS).unwrap();
+        assert_eq!(super::address(&private_key()).unwrap(), address);
+
+        let signature = sign_message(b"checkpoint", &private_key()).unwrap();
+        assert!(signature.v == 27 || signature.v == 28);
+        assert_eq!(recover_message(b"checkpoint", &signature).unwrap(), address);
+        assert_ne!(
+            recover_message(b"checkpoint!", &signature).unwrap(),
+            address
+        );
+
+        let compact = Signature::from_bytes(&signature.to_compact().unwrap().0).unwrap();
+        assert_eq!(recover_message(b"checkpoint", &compact).unwrap(), address);
+    }
+}
@@ -0,0 +1,817 @@
+//! Reading contract storage with the storage layout emitted by solc
+//!
+//! The layout is the `storageLayout` output of the compiler, e.g. from
+//! `solc --combined-json storage-layout` or the `outputSelection` of the standard JSON interface.
+//! Variables are addressed with Solidity-like paths such as `owner`, `balances[0xabc…].amount`,
+//! `allowed[1][0xabc…]`, `names["alice"]`, `history[3]` or `history.length`, which are resolved
+//! to a slot and an offset within that slot and then read with
+//! [eth_get_storage_at](crate::rpc::eth_get_storage_at).
+//!
+//! Indices of dynamic arrays are not checked against the length stored on chain.
+
+use crate::connector::MAX_BATCH_SIZE;
+use crate::rpc;
+use crate::transport::Request;
+use crate::types::{BlockId, BlockParameter, Bytes, H160, H256, U256};
+use crate::utils::{array_slot, mapping_slot, mapping_slot_bytes};
+use crate::{Connector, ConnectorError};
+use ethereum_types::BigEndianHash;
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+use std::str::FromStr;
+use thiserror::Error;
+
+// The type of `array.length`, which is not necessarily part of the layout
+const LENGTH_TYPE: &str = "t_uint256";
+
+/// The maximum length in bytes of long strings and bytes read from storage
+pub const MAX_BYTES_LENGTH: usize = 1 << 20;
+
+/// The storage layout of a contract as emitted by solc
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct StorageLayout {
+    pub storage: Vec<StorageEntry>,
+    /// The types used by the entries, keyed by type id. solc emits `null` for contracts without
+    /// state variables.
+    #[serde(deserialize_with = "deserialize_types")]
+    pub types: HashMap<String, StorageType>,
+}
+
+/// A state variable or a struct member
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct StorageEntry {
+    pub label: String,
+    /// The offset in bytes within the slot, counted from the lower-order end
+    pub offset: u8,
+    #[serde(deserialize_with = "deserialize_dec_u256")]
+    pub slot: U256,
+    /// The type id, which is a key into [StorageLayout::types]
+    #[serde(rename = "type")]
+    pub type_id: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageType {
+    pub encoding: StorageEncoding,
+    pub label: String,
+    #[serde(deserialize_with = "deserialize_dec_u256")]
+    pub number_of_bytes: U256,
+    /// The key type id of mappings
+    pub key: Option<String>,
+    /// The value type id of mappings
+    pub value: Option<String>,
+    /// The element type id of arrays
+    pub base: Option<String>,
+    /// The members of structs
+    pub members: Option<Vec<StorageEntry>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageEncoding {
+    Inplace,
+    Mapping,
+    DynamicArray,
+    Bytes,
+}
+
+/// The position of a value in storage
+#[derive(Clone, Debug, PartialEq)]
+pub struct StorageLocation {
+    pub slot: U256,
+    pub offset: u8,
+    pub type_id: String,
+}
+
+/// A value read from storage
+#[derive(Clone, Debug, PartialEq)]
+pub enum StorageValue {
+    /// Unsigned integers and enums
+    Uint(U256),
+    /// Signed integers in two's complement, sign extended to 256 bits
+    Int(U256),
+    /// Addresses and contracts
+    Address(H160),
+    Bool(bool),
+    FixedBytes(Vec<u8>),
+    Bytes(Vec<u8>),
+    String(String),
+}
+
+impl StorageLayout {
+    /// Resolves a path to the slot and offset of the value
+    pub fn locate(&self, path: &str) -> Result<StorageLocation, StorageError> {
+        let (root, segments) = parse_path(path)?;
+        let entry = self
+            .storage
+            .iter()
+            .find(|entry| entry.label == root)
+            .ok_or_else(|| StorageError::UnknownVariable(String::from(root)))?;
+        let mut location = StorageLocation {
+            slot: entry.slot,
+            offset: entry.offset,
+            type_id: entry.type_id.clone(),
+        };
+
+        for segment in segments {
+            let storage_type = self.storage_type(&location.type_id)?;
+            location = match (segment, storage_type.encoding) {
+                (Segment::Member("length"), StorageEncoding::DynamicArray) => StorageLocation {
+                    slot: location.slot,
+                    offset: 0,
+                    type_id: String::from(LENGTH_TYPE),
+                },
+                (Segment::Member(name), StorageEncoding::Inplace) => {
+                    let member = storage_type
+                        .members
+                        .as_ref()
+                        .and_then(|members| members.iter().find(|member| member.label == name))
+                        .ok_or_else(|| StorageError::InvalidPath(String::from(path)))?;
+                    StorageLocation {
+                        slot: location.slot.overflowing_add(member.slot).0,
+                        offset: member.offset,
+                        type_id: member.type_id.clone(),
+                    }
+                }
+                (Segment::Index(key), StorageEncoding::Mapping) => {
+                    let (key_type, value_type) = storage_type
+                        .key
+                        .as_ref()
+                        .zip(storage_type.value.as_ref())
+                        .ok_or_else(|| StorageError::UnknownType(storage_type.label.clone()))?;
+                    StorageLocation {
+                        slot: self.mapping_slot(key, key_type, location.slot)?,
+                        offset: 0,
+                        type_id: value_type.clone(),
+                    }
+                }
+                (Segment::Index(index), StorageEncoding::DynamicArray) => {
+                    let base = storage_type
+                        .base
+                        .as_ref()
+                        .ok_or_else(|| StorageError::UnknownType(storage_type.label.clone()))?;
+                    let index = parse_uint(index).ok_or_else(|| invalid_key(index))?;
+                    let (slot_index, slots_per_element, offset) = self.element(index, base)?;
+                    StorageLocation {
+                        slot: array_slot(location.slot, slot_index, slots_per_element),
+                        offset,
+                        type_id: base.clone(),
+                    }
+                }
+                (Segment::Index(index), StorageEncoding::Inplace)
+                    if storage_type.base.is_some() =>
+                {
+                    let base = storage_type.base.as_ref().unwrap();
+                    let index = parse_uint(index).ok_or_else(|| invalid_key(index))?;
+                    if let Some(length) = static_length(&storage_type.label) {
+                        if index >= length {
+                            return Err(StorageError::IndexOutOfBounds(index));
+                        }
+                    }
+                    let (slot_index, slots_per_element, offset) = self.element(index, base)?;
+                    StorageLocation {
+                        slot: location.slot + slot_index * slots_per_element,
+                        offset,
+                        type_id: base.clone(),
+                    }
+                }
+                _ => return Err(StorageError::InvalidPath(String::from(path))),
+            };
+        }
+        Ok(location)
+    }
+
+    /// Resolves a path and reads the value from the storage of the contract at `address`
+    ///
+    /// Paths have to end at a value type, a `string` or `bytes`. Long strings and bytes, which are
+    /// stored outside of their slot, are fetched with batch requests of at most [MAX_BATCH_SIZE]
+    /// slots and may not exceed [MAX_BYTES_LENGTH] bytes.
+    pub fn read<T: Request>(
+        &self,
+        connector: &mut Connector<T>,
+        address: H160,
+        path: &str,
+        block_param: Option<BlockParameter>,
+    ) -> Result<StorageValue, StorageError> {
+        let location = self.locate(path)?;
+        let (kind, size) = self.value_kind(&location.type_id)?;
+        let word = storage_word(connector.call(rpc::eth_get_storage_at(
+            address,
+            location.slot,
+            block_param.map(BlockId::from),
+        ))?)?;
+        if !matches!(kind, ValueKind::String | ValueKind::Bytes) {
+            return decode_value(kind, size, location.offset, &word);
+        }
+
+        let content = match bytes_slot(&word)? {
+            BytesSlot::Short(content) => content,
+            BytesSlot::Long(length) => {
+                let slots = length.div_ceil(32);
+                let mut content = Vec::new();
+                for start in (0..slots).step_by(MAX_BATCH_SIZE) {
+                    let rpcs = (start..slots.min(start + MAX_BATCH_SIZE))
+                        .map(|index| {
+                            let slot = array_slot(location.slot, U256::from(index), U256::one());
+                            rpc::eth_get_storage_at(address, slot, block_param.map(BlockId::from))
+                        })
+                        .collect();
+                    for word in connector.call_batch(rpcs)? {
+                        content.extend_from_slice(storage_word(word?)?.as_bytes());
+                    }
+                }
+                content.truncate(length);
+                content
+            }
+        };
+        match kind {
+            ValueKind::String => Ok(StorageValue::String(String::from_utf8(content)?)),
+            _ => Ok(StorageValue::Bytes(content)),
+        }
+    }
+
+    fn storage_type(&self, type_id: &str) -> Result<&StorageType, StorageError> {
+        self.types
+            .get(type_id)
+            .ok_or_else(|| StorageError::UnknownType(String::from(type_id)))
+    }
+
+    // Classifies value types by their type id, since labels of contracts and enums are names
+    fn value_kind(&self, type_id: &str) -> Result<(ValueKind, usize), StorageError> {
+        if type_id == LENGTH_TYPE && !self.types.contains_key(type_id) {
+            return Ok((ValueKind::Uint, 32));
+        }
+        let storage_type = self.storage_type(type_id)?;
+        let size = storage_type.number_of_bytes;
+        let kind = match storage_type.encoding {
+            StorageEncoding::Bytes if type_id.starts_with("t_string") => ValueKind::String,
+            StorageEncoding::Bytes => ValueKind::Bytes,
+            StorageEncoding::Inplace if size <= U256::from(32) => {
+                if type_id.starts_with("t_address") || type_id.starts_with("t_contract(") {
+                    ValueKind::Address
+                } else if type_id == "t_bool" {
+                    ValueKind::Bool
+                } else if type_id.starts_with("t_uint") || type_id.starts_with("t_enum(") {
+                    ValueKind::Uint
+                } else if type_id.starts_with("t_int") {
+                    ValueKind::Int
+                } else if type_id.starts_with("t_bytes") {
+                    ValueKind::FixedBytes
+                } else {
+                    return Err(StorageError::Unsupported(storage_type.label.clone()));
+                }
+            }
+            _ => return Err(StorageError::Unsupported(storage_type.label.clone())),
+        };
+        Ok((kind, size.low_u64() as usize))
+    }
+
+    fn mapping_slot(&self, key: &str, key_type: &str, slot: U256) -> Result<U256, StorageError> {
+        let (kind, size) = self.value_kind(key_type)?;
+        let word = match kind {
+            ValueKind::String => {
+                let key = key
+                    .strip_prefix('"')
+                    .and_then(|key| key.strip_suffix('"'))
+                    .unwrap_or(key);
+                return Ok(mapping_slot_bytes(key.as_bytes(), slot));
+            }
+            ValueKind::Bytes => {
+                let key =
+                    hex::decode(key.trim_start_matches("0x")).map_err(|_| invalid_key(key))?;
+                return Ok(mapping_slot_bytes(&key, slot));
+            }
+            ValueKind::Address => H256::from(
+                H160::from_str(key.trim_start_matches("0x")).map_err(|_| invalid_key(key))?,
+            ),
+            ValueKind::Bool => match key {
+                "true" => H256::from_low_u64_be(1),
+                "false" => H256::zero(),
+                _ => return Err(invalid_key(key)),
+            },
+            ValueKind::Uint => H256::from_uint(&parse_uint(key).ok_or_else(|| invalid_key(key))?),
+            ValueKind::Int => {
+                let value = match key.strip_prefix('-') {
+                    Some(magnitude) => {
+                        let magnitude = parse_uint(magnitude).ok_or_else(|| invalid_key(key))?;
+                        (!magnitude).overflowing_add(U256::one()).0
+                    }
+                    None => parse_uint(key).ok_or_else(|| invalid_key(key))?,
+                };
+                H256::from_uint(&value)
+            }
+            ValueKind::FixedBytes => {
+                let bytes =
+                    hex::decode(key.trim_start_matches("0x")).map_err(|_| invalid_key(key))?;
+                if bytes.len() > size {
+                    return Err(invalid_key(key));
+                }
+                let mut word = H256::zero();
+                word.0[..bytes.len()].copy_from_slice(&bytes);
+                word
+            }
+        };
+        Ok(mapping_slot(word, slot))
+    }
+
+    // Returns the slot index, the slots per element and the offset of an array element
+    fn element(&self, index: U256, base: &str) -> Result<(U256, U256, u8), StorageError> {
+        let size = self.storage_type(base)?.number_of_bytes;
+        if size > U256::from(16) {
+            // Elements take up whole slots
+            return Ok((index, (size + 31) / 32, 0));
+        }
+        // Small elements are packed into slots, starting at the lower-order end
+        let per_slot = U256::from(32) / size.max(U256::one());
+        let offset = (index % per_slot) * size;
+        Ok((index / per_slot, U256::one(), offset.low_u64() as u8))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ValueKind {
+    Uint,
+    Int,
+    Address,
+    Bool,
+    FixedBytes,
+    Bytes,
+    String,
+}
+
+enum Segment<'a> {
+    Member(&'a str),
+    Index(&'a str),
+}
+
+// Splits a path like `balances[0xabc].amount` into the variable name and the following segments
+fn parse_path(path: &str) -> Result<(&str, Vec<Segment<'_>>), StorageError> {
+    let invalid = || StorageError::InvalidPath(String::from(path));
+    let name_end = |rest: &str| rest.find(['.', '[']).unwrap_or(rest.len());
+
+    let (root, mut rest) = path.split_at(name_end(path));
+    if root.is_empty() {
+        return Err(invalid());
+    }
+    let mut segments = Vec::new();
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('.') {
+            let (name, after) = after.split_at(name_end(after));
+            if name.is_empty() {
+                return Err(invalid());
+            }
+            segments.push(Segment::Member(name));
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('[') {
+            // Quoted string keys may contain brackets
+            let key_start = match after.strip_prefix('"') {
+                Some(quoted) => quoted.find('"').ok_or_else(invalid)? + 2,
+                None => 0,
+            };
+            let key_end = key_start + after[key_start..].find(']').ok_or_else(invalid)?;
+            segments.push(Segment::Index(after[..key_end].trim()));
+            rest = &after[key_end + 1..];
+        } else {
+            return Err(invalid());
+        }
+    }
+    Ok((root, segments))
+}
+
+// Parses decimal or 0x-prefixed hex numbers
+fn parse_uint(value: &str) -> Option<U256> {
+    match value.strip_prefix("0x") {
+        Some(hex) => U256::from_str(hex).ok(),
+        None => U256::from_dec_str(value).ok(),
+    }
+}
+
+// Parses the length of a static array from a label like `uint256[3]`
+fn static_length(label: &str) -> Option<U256> {
+    let length = label.strip_suffix(']')?.rsplit('[').next()?;
+    U256::from_dec_str(length).ok()
+}
+
+fn invalid_key(key: &str) -> StorageError {
+    StorageError::InvalidKey(String::from(key))
+}
+
+fn storage_word(bytes: Bytes) -> Result<H256, StorageError> {
+    if bytes.0.len() > 32 {
+        return Err(StorageError::InvalidWord(bytes));
+    }
+    let mut word = H256::zero();
+    word.0[32 - bytes.0.len()..].copy_from_slice(&bytes.0);
+    Ok(word)
+}
+
+fn decode_value(
+    kind: ValueKind,
+    size: usize,
+    offset: u8,
+    word: &H256,
+) -> Result<StorageValue, StorageError> {
+    let end = 32usize
+        .checked_sub(offset as usize)
+        .filter(|end| *end >= size)
+        .ok_or(StorageError::InvalidOffset(offset))?;
+    let bytes = &word.as_bytes()[end - size..end];
+    let padded = |fill: u8| {
+        let mut padded = [fill; 32];
+        padded[32 - size..].copy_from_slice(bytes);
+        U256::from(padded)
+    };
+    let value = match kind {
+        ValueKind::Uint => StorageValue::Uint(padded(0)),
+        ValueKind::Int => match bytes.first() {
+            Some(byte) if byte & 0x80 != 0 => StorageValue::Int(padded(0xff)),
+            _ => StorageValue::Int(padded(0)),
+        },
+        ValueKind::Address => StorageValue::Address(H160::from_slice(&bytes[size - 20..])),
+        ValueKind::Bool => StorageValue::Bool(bytes.iter().any(|byte| *byte != 0)),
+        ValueKind::FixedBytes => StorageValue::FixedBytes(bytes.to_vec()),
+        ValueKind::Bytes | ValueKind::String => return Err(StorageError::InvalidOffset(offset)),
+    };
+    Ok(value)
+}
+
+#[derive(Debug, PartialEq)]
+enum BytesSlot {
+    /// Up to 31 bytes stored in the higher-order bytes of the slot itself
+    Short(Vec<u8>),
+    /// The length of content stored in consecutive slots starting at `keccak256(slot)`
+    Long(usize),
+}
+
+fn bytes_slot(word: &H256) -> Result<BytesSlot, StorageError> {
+    let last = word.0[31];
+    if last & 1 == 0 {
+        let length = (last / 2) as usize;
+        // Short content has at most 31 bytes, the last byte holds the length
+        if length > 31 {
+            return Err(StorageError::InvalidWord(Bytes(word.0.to_vec())));
+        }
+        return Ok(BytesSlot::Short(word.0[..length].to_vec()));
+    }
+    let length = (word.into_uint() - 1) / 2;
+    if length > U256::from(MAX_BYTES_LENGTH) {
+        return Err(StorageError::TooLong(length));
+    }
+    Ok(BytesSlot::Long(length.as_usize()))
+}
+
+fn deserialize_types<'de, D>(deserializer: D) -> Result<HashMap<String, StorageType>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<HashMap<String, StorageType>>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+fn deserialize_dec_u256<'de, D>(deserializer: D) -> Result<U256, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw = String::deserialize(deserializer)?;
+    U256::from_dec_str(&raw)
+        .map_err(|err| serde::de::Error::custom(format!("Invalid number {}: {:?}", raw, err)))
+}
+
+/// An error type collecting what can go wrong when locating or reading storage
+#[derive(Debug, Error)]
+pub enum StorageError {
+    #[error("Storage Error: {0}")]
+    Connector(Box<ConnectorError>),
+    #[error("Storage Error: No state variable {0}")]
+    UnknownVariable(String),
+    #[error("Storage Error: Invalid path {0}")]
+    InvalidPath(String),
+    #[error("Storage Error: Invalid key or index {0}")]
+    InvalidKey(String),
+    #[error("Storage Error: Index {0} is out of bounds")]
+    IndexOutOfBounds(U256),
+    #[error("Storage Error: Type {0} is missing in the layout")]
+    UnknownType(String),
+    #[error("Storage Error: Unable to decode values of type {0}")]
+    Unsupported(String),
+    #[error("Storage Error: Invalid offset {0}")]
+    InvalidOffset(u8),
+    #[error("Storage Error: Invalid storage word {0:?}")]
+    InvalidWord(Bytes),
+    #[error("Storage Error: Length {0} exceeds the maximum length of strings and bytes")]
+    TooLong(U256),
+    #[error("Storage Error: {0}")]
+    Utf8(#[from] std::string::FromUtf8Error),
+}
+
+impl From<ConnectorError> for StorageError {
+    fn from(err: ConnectorError) -> Self {
+        StorageError::Connector(Box::new(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::TransportError;
+    use serde_json::{json, Value};
+
+    // Serves storage slots, which default to zero, and records the size of every request
+    #[derive(Default)]
+    struct MockNode {
+        slots: HashMap<U256, H256>,
+        requests: Vec<usize>,
+    }
+
+    impl MockNode {
+        fn respond(&self, request: &Value) -> Value {
+            assert_eq!(request["method"], "eth_getStorageAt");
+            let slot = U256::from_str(&request["params"][1].as_str().unwrap()[2..]).unwrap();
+            let word = self.slots.get(&slot).copied().unwrap_or_default();
+            json!({"jsonrpc": "2.0", "id": request["id"], "result": word})
+        }
+    }
+
+    impl Request for MockNode {
+        fn request(&mut self, cmd: String) -> Result<String, TransportError> {
+            let response = match serde_json::from_str(&cmd).unwrap() {
+                Value::Array(requests) => {
+                    self.requests.push(requests.len());
+                    requests
+                        .iter()
+                        .map(|request| self.respond(request))
+                        .collect()
+                }
+                request => {
+                    self.requests.push(1);
+                    self.respond(&request)
+                }
+            };
+            Ok(response.to_string())
+        }
+    }
+
+    fn read(connector: &mut Connector<MockNode>, path: &str) -> Result<StorageValue, StorageError> {
+        let address = H160::from_str(ADDRESS).unwrap();
+        layout().read(connector, address, path, None)
+    }
+
+    // Layout of
+    //
+    // contract Bank {
+    //     struct Account { uint128 amount; uint64 since; bool frozen; address owner; }
+    //     uint8 small; bool flag; address admin;
+    //     mapping(address => Account) balances;
+    //     uint256[] history;
+    //     uint16[3] packed;
+    //     string name;
+    //     mapping(string => uint256) byName;
+    //     mapping(uint256 => mapping(address => bool)) allowed;
+    //     int32 delta;
+    // }
+    const LAYOUT: &str = r#"{
+        "storage": [
+            {"astId": 12, "contract": "Bank.sol:Bank", "label": "small", "offset": 0, "slot": "0", "type": "t_uint8"},
+            {"astId": 14, "contract": "Bank.sol:Bank", "label": "flag", "offset": 1, "slot": "0", "type": "t_bool"},
+            {"astId": 16, "contract": "Bank.sol:Bank", "label": "admin", "offset": 2, "slot": "0", "type": "t_address"},
+            {"astId": 21, "contract": "Bank.sol:Bank", "label": "balances", "offset": 0, "slot": "1", "type": "t_mapping(t_address,t_struct(Account)10_storage)"},
+            {"astId": 24, "contract": "Bank.sol:Bank", "label": "history", "offset": 0, "slot": "2", "type": "t_array(t_uint256)dyn_storage"},
+            {"astId": 28, "contract": "Bank.sol:Bank", "label": "packed", "offset": 0, "slot": "3", "type": "t_array(t_uint16)3_storage"},
+            {"astId": 30, "contract": "Bank.sol:Bank", "label": "name", "offset": 0, "slot": "4", "type": "t_string_storage"},
+            {"astId": 34, "contract": "Bank.sol:Bank", "label": "byName", "offset": 0, "slot": "5", "type": "t_mapping(t_string_memory_ptr,t_uint256)"},
+            {"astId": 40, "contract": "Bank.sol:Bank", "label": "allowed", "offset": 0, "slot": "6", "type": "t_mapping(t_uint256,t_mapping(t_address,t_bool))"},
+            {"astId": 42, "contract": "Bank.sol:Bank", "label": "delta", "offset": 0, "slot": "7", "type": "t_int32"}
+        ],
+        "types": {
+            "t_address": {"encoding": "inplace", "label": "address", "numberOfBytes": "20"},
+            "t_array(t_uint16)3_storage": {"base": "t_uint16", "encoding": "inplace", "label": "uint16[3]", "numberOfBytes": "32"},
+            "t_array(t_uint256)dyn_storage": {"base": "t_uint256", "encoding": "dynamic_array", "label": "uint256[]", "numberOfBytes": "32"},
+            "t_bool": {"encoding": "inplace", "label": "bool", "numberOfBytes": "1"},
+            "t_int32": {"encoding": "inplace", "label": "int32", "numberOfBytes": "4"},
+            "t_mapping(t_address,t_bool)": {"encoding": "mapping", "key": "t_address", "label": "mapping(address => bool)", "numberOfBytes": "32", "value": "t_bool"},
+            "t_mapping(t_address,t_struct(Account)10_storage)": {"encoding": "mapping", "key": "t_address", "label": "mapping(address => struct Bank.Account)", "numberOfBytes": "32", "value": "t_struct(Account)10_storage"},
+            "t_mapping(t_string_memory_ptr,t_uint256)": {"encoding": "mapping", "key": "t_string_memory_ptr", "label": "mapping(string => uint256)", "numberOfBytes": "32", "value": "t_uint256"},
+            "t_mapping(t_uint256,t_mapping(t_address,t_bool))": {"encoding": "mapping", "key": "t_uint256", "label": "mapping(uint256 => mapping(address => bool))", "numberOfBytes": "32", "value": "t_mapping(t_address,t_bool)"},
+            "t_string_memory_ptr": {"encoding": "bytes", "label": "string", "numberOfBytes": "32"},
+            "t_string_storage": {"encoding": "bytes", "label": "string", "numberOfBytes": "32"},
+            "t_struct(Account)10_storage": {"encoding": "inplace", "label": "struct Bank.Account", "numberOfBytes": "64", "members": [
+                {"astId": 3, "contract": "Bank.sol:Bank", "label": "amount", "offset": 0, "slot": "0", "type": "t_uint128"},
+                {"astId": 5, "contract": "Bank.sol:Bank", "label": "since", "offset": 16, "slot": "0", "type": "t_uint64"},
+                {"astId": 7, "contract": "Bank.sol:Bank", "label": "frozen", "offset": 24, "slot": "0", "type": "t_bool"},
+                {"astId": 9, "contract": "Bank.sol:Bank", "label": "owner", "offset": 0, "slot": "1", "type": "t_address"}
+            ]},
+            "t_uint128": {"encoding": "inplace", "label": "uint128", "numberOfBytes": "16"},
+            "t_uint16": {"encoding": "inplace", "label": "uint16", "numberOfBytes": "2"},
+            "t_uint256": {"encoding": "inplace", "label": "uint256", "numberOfBytes": "32"},
+            "t_uint64": {"encoding": "inplace", "label": "uint64", "numberOfBytes": "8"},
+            "t_uint8": {"encoding": "inplace", "label": "uint8", "numberOfBytes": "1"}
+        }
+    }"#;
+
+    const ADDRESS: &str = "0x0000000000000000000000000000000000000001";
+
+    fn layout() -> StorageLayout {
+        serde_json::from_str(LAYOUT).unwrap()
+    }
+
+    fn location(slot: U256, offset: u8, type_id: &str) -> StorageLocation {
+        StorageLocation {
+            slot,
+            offset,
+            type_id: String::from(type_id),
+        }
+    }
+
+    #[test]
+    fn test_storage_locate() {
+        let layout = layout();
+        let address = H256::from(H160::from_str(ADDRESS).unwrap());
+
+        assert_eq!(
+            layout.locate("admin").unwrap(),
+            location(U256::zero(), 2, "t_address")
+        );
+        let account = mapping_slot(address, U256::one());
+        assert_eq!(
+            layout
+                .locate(&format!("balances[{}].since", ADDRESS))
+                .unwrap(),
+            location(account, 16, "t_uint64")
+        );
+        assert_eq!(
+            layout
+                .locate(&format!("balances[{}].owner", ADDRESS))
+                .unwrap(),
+            location(account + 1, 0, "t_address")
+        );
+        assert_eq!(
+            layout.locate("history[2]").unwrap(),
+            location(
+                array_slot(U256::from(2), U256::from(2), U256::one()),
+                0,
+                "t_uint256"
+            )
+        );
+        assert_eq!(
+            layout.locate("history.length").unwrap(),
+            location(U256::from(2), 0, "t_uint256")
+        );
+        assert_eq!(
+            layout.locate("packed[2]").unwrap(),
+            location(U256::from(3), 4, "t_uint16")
+        );
+        assert_eq!(
+            layout.locate(r#"byName["a]b"]"#).unwrap(),
+            location(mapping_slot_bytes(b"a]b", U256::from(5)), 0, "t_uint256")
+        );
+        assert_eq!(
+            layout
+                .locate(&format!("allowed[0x07][{}]", ADDRESS))
+                .unwrap(),
+            location(
+                mapping_slot(
+                    address,
+                    mapping_slot(H256::from_low_u64_be(7), U256::from(6))
+                ),
+                0,
+                "t_bool"
+            )
+        );
+    }
+
+    #[test]
+    fn test_storage_locate_errors() {
+        let layout = layout();
+        assert!(matches!(
+            layout.locate("missing"),
+            Err(StorageError::UnknownVariable(_))
+        ));
+        assert!(matches!(
+            layout.locate("balances.amount"),
+            Err(StorageError::InvalidPath(_))
+        ));
+        assert!(matches!(
+            layout.locate("history[1"),
+            Err(StorageError::InvalidPath(_))
+        ));
+        assert!(matches!(
+            layout.locate("balances[xyz]"),
+            Err(StorageError::InvalidKey(_))
+        ));
+        assert!(matches!(
+            layout.locate("packed[3]"),
+            Err(StorageError::IndexOutOfBounds(_))
+        ));
+    }
+
+    #[test]
+    fn test_storage_decode() {
+        let layout = layout();
+        let decode = |path: &str, word: &H256| {
+            let location = layout.locate(path).unwrap();
+            let (kind, size) = layout.value_kind(&location.type_id).unwrap();
+            decode_value(kind, size, location.offset, word).unwrap()
+        };
+
+        // small = 5, flag = true and admin = 0x..01 packed into slot 0
+        let mut word = H256::zero();
+        word.0[31] = 5;
+        word.0[30] = 1;
+        word.0[29] = 1;
+        assert_eq!(decode("small", &word), StorageValue::Uint(U256::from(5)));
+        assert_eq!(decode("flag", &word), StorageValue::Bool(true));
+        assert_eq!(
+            decode("admin", &word),
+            StorageValue::Address(H160::from_str(ADDRESS).unwrap())
+        );
+
+        let mut word = H256::zero();
+        word.0[28..].copy_from_slice(&[0xff, 0xff, 0xff, 0xfe]);
+        assert_eq!(decode("delta", &word), StorageValue::Int(U256::MAX - 1));
+
+        assert!(matches!(
+            layout.value_kind(
+                &layout
+                    .locate(&format!("balances[{}]", ADDRESS))
+                    .unwrap()
+                    .type_id
+            ),
+            Err(StorageError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_storage_bytes_slot() {
+        let mut word = H256::zero();
+        word.0[..3].copy_from_slice(b"abc");
+        word.0[31] = 6;
+        assert_eq!(
+            bytes_slot(&word).unwrap(),
+            BytesSlot::Short(b"abc".to_vec())
+        );
+        assert_eq!(
+            bytes_slot(&H256::from_low_u64_be(65)).unwrap(),
+            BytesSlot::Long(32)
+        );
+        assert!(matches!(
+            bytes_slot(&H256::from_low_u64_be(0xfe)),
+            Err(StorageError::InvalidWord(_))
+        ));
+    }
+
+    #[test]
+    fn test_storage_read_values() {
+        let mut node = MockNode::default();
+        // small = 7, flag = true and admin = 0x..01 packed into slot 0, where the odd last byte
+        // must not be mistaken for the length of a long string
+        let mut word = H256::zero();
+        word.0[31] = 7;
+        word.0[30] = 1;
+        word.0[29] = 1;
+        node.slots.insert(U256::zero(), word);
+        node.slots.insert(U256::from(2), H256::repeat_byte(0xff));
+        let mut connector = Connector::with_transport(node);
+
+        assert_eq!(
+            read(&mut connector, "admin").unwrap(),
+            StorageValue::Address(H160::from_str(ADDRESS).unwrap())
+        );
+        assert_eq!(
+            read(&mut connector, "small").unwrap(),
+            StorageValue::Uint(U256::from(7))
+        );
+        assert_eq!(
+            read(&mut connector, "history.length").unwrap(),
+            StorageValue::Uint(U256::MAX)
+        );
+        assert_eq!(connector.transport().requests, vec![1, 1, 1]);
+    }
+
+    #[test]
+    fn test_storage_read_long_string() {
+        let length = 32 * (MAX_BATCH_SIZE + 1) - 5;
+        let mut node = MockNode::default();
+        node.slots
+            .insert(U256::from(4), H256::from_uint(&U256::from(length * 2 + 1)));
+        for index in 0..MAX_BATCH_SIZE + 1 {
+            let slot = array_slot(U256::from(4), U256::from(index), U256::one());
+            node.slots.insert(slot, H256::repeat_byte(b'a'));
+        }
+        let mut connector = Connector::with_transport(node);
+
+        assert_eq!(
+            read(&mut connector, "name").unwrap(),
+            StorageValue::String("a".repeat(length))
+        );
+        assert_eq!(connector.transport().requests, vec![1, MAX_BATCH_SIZE, 1]);
+
+        connector.transport_mut().slots.insert(
+            U256::from(4),
+            H256::from_uint(&U256::from((MAX_BYTES_LENGTH + 1) * 2 + 1)),
+        );
+        assert!(matches!(
+            read(&mut connector, "name"),
+            Err(StorageError::TooLong(length)) if length == U256::from(MAX_BYTES_LENGTH + 1)
+        ));
+    }
+}
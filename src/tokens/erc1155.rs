@@ -0,0 +1,105 @@
+use super::{call, encode_function, transaction, uint_array, TokenError};
+use crate::abi::{Decoder, Token};
+use crate::transport::Request;
+use crate::types::{BlockParameter, Bytes, TransactionRequest, H160, U256};
+use crate::Connector;
+
+/// A multi token ERC-1155 contract
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Erc1155 {
+    pub address: H160,
+}
+
+impl Erc1155 {
+    pub fn new(address: H160) -> Self {
+        Erc1155 { address }
+    }
+
+    pub fn balance_of<T: Request>(
+        &self,
+        connector: &mut Connector<T>,
+        owner: H160,
+        id: U256,
+        block_param: Option<BlockParameter>,
+    ) -> Result<U256, TokenError> {
+        let data = encode_function(
+            "balanceOf(address,uint256)",
+            &[Token::Address(owner), Token::Uint(id)],
+        );
+        let output = call(connector, self.address, data, block_param)?;
+        Ok(Decoder::new(&output.0).uint(0)?)
+    }
+
+    /// Returns the balance of every pair of `owners` and `ids` with a single call
+    pub fn balance_of_batch<T: Request>(
+        &self,
+        connector: &mut Connector<T>,
+        owners: &[H160],
+        ids: &[U256],
+        block_param: Option<BlockParameter>,
+    ) -> Result<Vec<U256>, TokenError> {
+        let data = encode_function(
+            "balanceOfBatch(address[],uint256[])",
+            &[
+                Token::Array(owners.iter().copied().map(Token::Address).collect()),
+                Token::Array(ids.iter().copied().map(Token::Uint).collect()),
+            ],
+        );
+        let output = call(connector, self.address, data, block_param)?;
+        Ok(uint_array(&Decoder::new(&output.0), 0)?)
+    }
+
+    /// Transfers `value` tokens of type `id`, passing `data` to contract recipients
+    pub fn safe_transfer_from(
+        &self,
+        from: H160,
+        to: H160,
+        id: U256,
+        value: U256,
+        data: Bytes,
+    ) -> TransactionRequest {
+        let data = encode_function(
+            "safeTransferFrom(address,address,uint256,uint256,bytes)",
+            &[
+                Token::Address(from),
+                Token::Address(to),
+                Token::Uint(id),
+                Token::Uint(value),
+                Token::Bytes(data.0),
+            ],
+        );
+        transaction(from, self.address, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_erc1155_safe_transfer_from() {
+        let token = Erc1155::new(H160::from_low_u64_be(0x70));
+        let transaction = token.safe_transfer_from(
+            H160::from_low_u64_be(1),
+            H160::from_low_u64_be(2),
+            U256::from(5),
+            U256::from(10),
+            Bytes::default(),
+        );
+        assert_eq!(transaction.from, H160::from_low_u64_be(1));
+        assert_eq!(
+            transaction.data.unwrap(),
+            Bytes::from_str(concat!(
+                "0xf242432a",
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "0000000000000000000000000000000000000000000000000000000000000002",
+                "0000000000000000000000000000000000000000000000000000000000000005",
+                "000000000000000000000000000000000000000000000000000000000000000a",
+                "00000000000000000000000000000000000000000000000000000000000000a0",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+            ))
+            .unwrap()
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use super::{call, encode_function, transaction, TokenError};
+use crate::abi::{Decoder, Token};
+use crate::multicall::Multicall;
+use crate::transport::Request;
+use crate::types::{BlockParameter, Bytes, Call, TransactionRequest, H160, U256};
+use crate::Connector;
+
+/// A fungible ERC-20 token contract
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Erc20 {
+    pub address: H160,
+}
+
+impl Erc20 {
+    pub fn new(address: H160) -> Self {
+        Erc20 { address }
+    }
+
+    pub fn balance_of<T: Request>(
+        &self,
+        connector: &mut Connector<T>,
+        owner: H160,
+        block_param: Option<BlockParameter>,
+    ) -> Result<U256, TokenError> {
+        let output = call(connector, self.address, balance_of_data(owner), block_param)?;
+        Ok(Decoder::new(&output.0).uint(0)?)
+    }
+
+    /// Queries the balances of many owners with a single [Multicall]
+    ///
+    /// The Multicall3 contract is expected at `multicall_address`, which defaults to
+    /// [MULTICALL3_ADDRESS](crate::multicall::MULTICALL3_ADDRESS).
+    pub fn balances_of<T: Request>(
+        &self,
+        connector: &mut Connector<T>,
+        owners: &[H160],
+        multicall_address: Option<H160>,
+        block_param: Option<BlockParameter>,
+    ) -> Result<Vec<U256>, TokenError> {
+        let mut multicall = Multicall::new(multicall_address);
+        for owner in owners {
+            multicall.add_call(Call {
+                to: self.address,
+                data: Some(balance_of_data(*owner)),
+                ..Default::default()
+            });
+        }
+        multicall
+            .call(connector, block_param)?
+            .into_iter()
+            .map(|result| {
+                if !result.success {
+                    return Err(TokenError::CallFailed);
+                }
+                Ok(Decoder::new(&result.return_data.0).uint(0)?)
+            })
+            .collect()
+    }
+
+    pub fn allowance<T: Request>(
+        &self,
+        connector: &mut Connector<T>,
+        owner: H160,
+        spender: H160,
+        block_param: Option<BlockParameter>,
+    ) -> Result<U256, TokenError> {
+        let data = encode_function(
+            "allowance(address,address)",
+            &[Token::Address(owner), Token::Address(spender)],
+        );
+        let output = call(connector, self.address, data, block_param)?;
+        Ok(Decoder::new(&output.0).uint(0)?)
+    }
+
+    pub fn decimals<T: Request>(
+        &self,
+        connector: &mut Connector<T>,
+        block_param: Option<BlockParameter>,
+    ) -> Result<u8, TokenError> {
+        let data = encode_function("decimals()", &[]);
+        let output = call(connector, self.address, data, block_param)?;
+        Ok(Decoder::new(&output.0).uint8(0)?)
+    }
+
+    /// Returns the symbol of the token
+    ///
+    /// Some early tokens return the symbol as `bytes32` instead of `string`, which is supported
+    /// as well.
+    pub fn symbol<T: Request>(
+        &self,
+        connector: &mut Connector<T>,
+        block_param: Option<BlockParameter>,
+    ) -> Result<String, TokenError> {
+        let data = encode_function("symbol()", &[]);
+        let output = call(connector, self.address, data, block_param)?;
+        decode_symbol(&output)
+    }
+
+    pub fn transfer(&self, from: H160, to: H160, value: U256) -> TransactionRequest {
+        let data = encode_function(
+            "transfer(address,uint256)",
+            &[Token::Address(to), Token::Uint(value)],
+        );
+        transaction(from, self.address, data)
+    }
+
+    pub fn approve(&self, from: H160, spender: H160, value: U256) -> TransactionRequest {
+        let data = encode_function(
+            "approve(address,uint256)",
+            &[Token::Address(spender), Token::Uint(value)],
+        );
+        transaction(from, self.address, data)
+    }
+}
+
+fn balance_of_data(owner: H160) -> Bytes {
+    encode_function("balanceOf(address)", &[Token::Address(owner)])
+}
+
+pub(super) fn decode_symbol(output: &Bytes) -> Result<String, TokenError> {
+    if output.0.len() == 32 {
+        let symbol = output.0.iter().take_while(|byte| **byte != 0).copied();
+        return Ok(String::from_utf8_lossy(&symbol.collect::<Vec<u8>>()).into_owned());
+    }
+    Ok(Decoder::new(&output.0).string(0)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_erc20_transfer() {
+        let token = Erc20::new(H160::from_low_u64_be(0x70));
+        let transaction = token.transfer(
+            H160::from_low_u64_be(1),
+            H160::from_low_u64_be(2),
+            U256::from(1000),
+        );
+        assert_eq!(transaction.to, Some(token.address));
+        assert_eq!(
+            transaction.data.unwrap(),
+            Bytes::from_str(concat!(
+                "0xa9059cbb",
+                "0000000000000000000000000000000000000000000000000000000000000002",
+                "00000000000000000000000000000000000000000000000000000000000003e8",
+            ))
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_erc20_symbol() {
+        let bytes32 =
+            Bytes::from_str("0x4d4b520000000000000000000000000000000000000000000000000000000000")
+                .unwrap();
+        assert_eq!(decode_symbol(&bytes32).unwrap(), "MKR");
+
+        let string = Bytes::from_str(concat!(
+            "0x",
+            "0000000000000000000000000000000000000000000000000000000000000020",
+            "0000000000000000000000000000000000000000000000000000000000000003",
+            "4441490000000000000000000000000000000000000000000000000000000000",
+        ))
+        .unwrap();
+        assert_eq!(decode_symbol(&string).unwrap(), "DAI");
+    }
+}
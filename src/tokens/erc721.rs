@@ -0,0 +1,72 @@
+use super::erc20::decode_symbol;
+use super::{call, encode_function, transaction, TokenError};
+use crate::abi::{Decoder, Token};
+use crate::transport::Request;
+use crate::types::{BlockParameter, TransactionRequest, H160, U256};
+use crate::Connector;
+
+/// A non-fungible ERC-721 token contract
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Erc721 {
+    pub address: H160,
+}
+
+impl Erc721 {
+    pub fn new(address: H160) -> Self {
+        Erc721 { address }
+    }
+
+    /// Returns the number of tokens held by `owner`
+    pub fn balance_of<T: Request>(
+        &self,
+        connector: &mut Connector<T>,
+        owner: H160,
+        block_param: Option<BlockParameter>,
+    ) -> Result<U256, TokenError> {
+        let data = encode_function("balanceOf(address)", &[Token::Address(owner)]);
+        let output = call(connector, self.address, data, block_param)?;
+        Ok(Decoder::new(&output.0).uint(0)?)
+    }
+
+    pub fn owner_of<T: Request>(
+        &self,
+        connector: &mut Connector<T>,
+        token_id: U256,
+        block_param: Option<BlockParameter>,
+    ) -> Result<H160, TokenError> {
+        let data = encode_function("ownerOf(uint256)", &[Token::Uint(token_id)]);
+        let output = call(connector, self.address, data, block_param)?;
+        Ok(Decoder::new(&output.0).address(0)?)
+    }
+
+    pub fn symbol<T: Request>(
+        &self,
+        connector: &mut Connector<T>,
+        block_param: Option<BlockParameter>,
+    ) -> Result<String, TokenError> {
+        let data = encode_function("symbol()", &[]);
+        let output = call(connector, self.address, data, block_param)?;
+        decode_symbol(&output)
+    }
+
+    pub fn approve(&self, from: H160, approved: H160, token_id: U256) -> TransactionRequest {
+        let data = encode_function(
+            "approve(address,uint256)",
+            &[Token::Address(approved), Token::Uint(token_id)],
+        );
+        transaction(from, self.address, data)
+    }
+
+    /// Transfers a token, which checks that contract recipients are able to receive it
+    pub fn safe_transfer_from(&self, from: H160, to: H160, token_id: U256) -> TransactionRequest {
+        let data = encode_function(
+            "safeTransferFrom(address,address,uint256)",
+            &[
+                Token::Address(from),
+                Token::Address(to),
+                Token::Uint(token_id),
+            ],
+        );
+        transaction(from, self.address, data)
+    }
+}
@@ -0,0 +1,310 @@
+//! Typed wrappers for ERC-20, ERC-721 and ERC-1155 token contracts
+//!
+//! Read-only functions are executed with [eth_call](crate::rpc::eth_call) and return decoded
+//! values. State changing functions return a [TransactionRequest], which can be sent with
+//! [eth_send_transaction](crate::rpc::eth_send_transaction) or signed first.
+//!
+//! Events emitted by token contracts are decoded from [logs](crate::types::Log) with
+//! [TokenEvent::from_log].
+
+use crate::abi::{encode_call, AbiError, Decoder, Token};
+use crate::multicall::MulticallError;
+use crate::rpc;
+use crate::transport::Request;
+use crate::types::{BlockId, BlockParameter, Bytes, Call, Log, TransactionRequest, H160, U256};
+use crate::utils::{event_topic, selector};
+use crate::{Connector, ConnectorError};
+use thiserror::Error;
+
+mod erc1155;
+mod erc20;
+mod erc721;
+
+pub use erc1155::Erc1155;
+pub use erc20::Erc20;
+pub use erc721::Erc721;
+
+/// An event of the token standards, decoded from a log
+#[derive(Clone, Debug, PartialEq)]
+pub enum TokenEvent {
+    /// `Transfer` of an ERC-20 token
+    Transfer { from: H160, to: H160, value: U256 },
+    /// `Approval` of an ERC-20 token
+    Approval {
+        owner: H160,
+        spender: H160,
+        value: U256,
+    },
+    /// `Transfer` of an ERC-721 token, which indexes the token id
+    NftTransfer {
+        from: H160,
+        to: H160,
+        token_id: U256,
+    },
+    /// `Approval` of an ERC-721 token, which indexes the token id
+    NftApproval {
+        owner: H160,
+        approved: H160,
+        token_id: U256,
+    },
+    /// `TransferSingle` of an ERC-1155 token
+    TransferSingle {
+        operator: H160,
+        from: H160,
+        to: H160,
+        id: U256,
+        value: U256,
+    },
+    /// `TransferBatch` of an ERC-1155 token
+    TransferBatch {
+        operator: H160,
+        from: H160,
+        to: H160,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+    },
+}
+
+impl TokenEvent {
+    /// Decodes a log of one of the supported events
+    ///
+    /// ERC-20 and ERC-721 share the event signatures and are told apart by the number of topics.
+    pub fn from_log(log: &Log) -> Result<Self, TokenError> {
+        let topic = |index: usize| log.topics.get(index).ok_or(TokenError::UnknownEvent);
+        let address = |index: usize| topic(index).map(|topic| H160::from(*topic));
+        let uint = |index: usize| topic(index).map(|topic| U256::from(topic.as_bytes()));
+        let data = Decoder::new(&log.data.0);
+
+        let signature = *topic(0)?;
+        let event = if signature == event_topic("Transfer(address,address,uint256)") {
+            match log.topics.len() {
+                3 => TokenEvent::Transfer {
+                    from: address(1)?,
+                    to: address(2)?,
+                    value: data.uint(0)?,
+                },
+                4 => TokenEvent::NftTransfer {
+                    from: address(1)?,
+                    to: address(2)?,
+                    token_id: uint(3)?,
+                },
+                _ => return Err(TokenError::UnknownEvent),
+            }
+        } else if signature == event_topic("Approval(address,address,uint256)") {
+            match log.topics.len() {
+                3 => TokenEvent::Approval {
+                    owner: address(1)?,
+                    spender: address(2)?,
+                    value: data.uint(0)?,
+                },
+                4 => TokenEvent::NftApproval {
+                    owner: address(1)?,
+                    approved: address(2)?,
+                    token_id: uint(3)?,
+                },
+                _ => return Err(TokenError::UnknownEvent),
+            }
+        } else if signature
+            == event_topic("TransferSingle(address,address,address,uint256,uint256)")
+        {
+            TokenEvent::TransferSingle {
+                operator: address(1)?,
+                from: address(2)?,
+                to: address(3)?,
+                id: data.uint(0)?,
+                value: data.uint(1)?,
+            }
+        } else if signature
+            == event_topic("TransferBatch(address,address,address,uint256[],uint256[])")
+        {
+            TokenEvent::TransferBatch {
+                operator: address(1)?,
+                from: address(2)?,
+                to: address(3)?,
+                ids: uint_array(&data, 0)?,
+                values: uint_array(&data, 1)?,
+            }
+        } else {
+            return Err(TokenError::UnknownEvent);
+        };
+        Ok(event)
+    }
+}
+
+/// An error type collecting what can go wrong when interacting with tokens
+#[derive(Debug, Error)]
+pub enum TokenError {
+    #[error("Token Error: {0}")]
+    Connector(Box<ConnectorError>),
+    #[error("Token Error: {0}")]
+    Multicall(#[from] MulticallError),
+    #[error("Token Error: Unable to decode output: {0}")]
+    Abi(#[from] AbiError),
+    #[error("Token Error: Call to token contract failed")]
+    CallFailed,
+    #[error("Token Error: Log is no known token event")]
+    UnknownEvent,
+}
+
+impl From<ConnectorError> for TokenError {
+    fn from(err: ConnectorError) -> Self {
+        TokenError::Connector(Box::new(err))
+    }
+}
+
+fn encode_function(signature: &str, tokens: &[Token]) -> Bytes {
+    encode_call(selector(signature), tokens)
+}
+
+fn call<T: Request>(
+    connector: &mut Connector<T>,
+    token: H160,
+    data: Bytes,
+    block_param: Option<BlockParameter>,
+) -> Result<Bytes, TokenError> {
+    let call = Call {
+        to: token,
+        data: Some(data),
+        ..Default::default()
+    };
+    Ok(connector.call(rpc::eth_call(
+        call,
+        block_param.map(BlockId::from),
+        None,
+        None,
+    ))?)
+}
+
+fn transaction(from: H160, token: H160, data: Bytes) -> TransactionRequest {
+    TransactionRequest {
+        from,
+        to: Some(token),
+        data: Some(data),
+        ..Default::default()
+    }
+}
+
+fn uint_array(decoder: &Decoder, index: usize) -> Result<Vec<U256>, AbiError> {
+    let (len, elements) = decoder.array(index)?;
+    (0..len).map(|index| elements.uint(index)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::H256;
+    use std::str::FromStr;
+
+    fn log(topics: Vec<H256>, data: &str) -> Log {
+        Log {
+            address: H160::from_low_u64_be(0x70),
+            topics,
+            data: Bytes::from_str(data).unwrap(),
+            block_hash: None,
+            block_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            removed: false,
+        }
+    }
+
+    fn address_topic(value: u64) -> H256 {
+        H256::from(H160::from_low_u64_be(value))
+    }
+
+    #[test]
+    fn test_tokens_transfer_events() {
+        let transfer_topic =
+            H256::from_str("0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef")
+                .unwrap();
+        let erc20 = log(
+            vec![transfer_topic, address_topic(1), address_topic(2)],
+            "0x00000000000000000000000000000000000000000000000000000000000003e8",
+        );
+        assert_eq!(
+            TokenEvent::from_log(&erc20).unwrap(),
+            TokenEvent::Transfer {
+                from: H160::from_low_u64_be(1),
+                to: H160::from_low_u64_be(2),
+                value: U256::from(1000),
+            }
+        );
+
+        let erc721 = log(
+            vec![
+                transfer_topic,
+                address_topic(1),
+                address_topic(2),
+                H256::from_low_u64_be(7),
+            ],
+            "0x",
+        );
+        assert_eq!(
+            TokenEvent::from_log(&erc721).unwrap(),
+            TokenEvent::NftTransfer {
+                from: H160::from_low_u64_be(1),
+                to: H160::from_low_u64_be(2),
+                token_id: U256::from(7),
+            }
+        );
+
+        let unknown = log(vec![H256::zero()], "0x");
+        assert!(matches!(
+            TokenEvent::from_log(&unknown),
+            Err(TokenError::UnknownEvent)
+        ));
+    }
+
+    #[test]
+    fn test_tokens_erc1155_events() {
+        let single = log(
+            vec![
+                event_topic("TransferSingle(address,address,address,uint256,uint256)"),
+                address_topic(3),
+                address_topic(1),
+                address_topic(2),
+            ],
+            concat!(
+                "0x",
+                "0000000000000000000000000000000000000000000000000000000000000005",
+                "000000000000000000000000000000000000000000000000000000000000000a",
+            ),
+        );
+        assert_eq!(
+            TokenEvent::from_log(&single).unwrap(),
+            TokenEvent::TransferSingle {
+                operator: H160::from_low_u64_be(3),
+                from: H160::from_low_u64_be(1),
+                to: H160::from_low_u64_be(2),
+                id: U256::from(5),
+                value: U256::from(10),
+            }
+        );
+
+        let data = crate::abi::encode(&[
+            Token::Array(vec![Token::Uint(1.into()), Token::Uint(2.into())]),
+            Token::Array(vec![Token::Uint(10.into()), Token::Uint(20.into())]),
+        ]);
+        let batch = log(
+            vec![
+                event_topic("TransferBatch(address,address,address,uint256[],uint256[])"),
+                address_topic(3),
+                address_topic(1),
+                address_topic(2),
+            ],
+            &hex::encode(data),
+        );
+        assert_eq!(
+            TokenEvent::from_log(&batch).unwrap(),
+            TokenEvent::TransferBatch {
+                operator: H160::from_low_u64_be(3),
+                from: H160::from_low_u64_be(1),
+                to: H160::from_low_u64_be(2),
+                ids: vec![U256::from(1), U256::from(2)],
+                values: vec![U256::from(10), U256::from(20)],
+            }
+        );
+    }
+}
@@ -1,7 +1,9 @@
 //! Possible transports which are supported by the [Connector](crate::Connector)
 
+use jwt::JwtSecret;
 use thiserror::Error;
 pub mod http;
+pub mod jwt;
 #[cfg(target_family = "unix")]
 pub mod uds;
 pub mod websocket;
@@ -25,10 +27,14 @@ pub trait Subscribe {
 /// So you can easily add HTTP Basic or JWT authentication. This will add an authorization header
 /// to your requests and works for [websockets](crate::Connector::websocket) and
 /// [http](crate::Connector::http).
+///
+/// For the Engine API use [Jwt](Self::Jwt), which signs a fresh token for every http request
+/// and websocket handshake.
 #[derive(Debug, Clone)]
 pub enum Credentials {
     Bearer(String),
     Basic(String),
+    Jwt(JwtSecret),
 }
 
 impl Credentials {
@@ -36,6 +42,7 @@ impl Credentials {
         match self {
             Self::Bearer(token) => String::from("Bearer ") + &token,
             Self::Basic(token) => String::from("Basic ") + &token,
+            Self::Jwt(secret) => String::from("Bearer ") + &secret.token(),
         }
     }
 }
@@ -68,5 +75,9 @@ mod tests {
             Credentials::Basic(auth_string.to_string()).to_auth_string(),
             String::from("Basic ") + auth_string
         );
+        let secret = jwt::JwtSecret::from_hex(&"11".repeat(32)).unwrap();
+        assert!(Credentials::Jwt(secret)
+            .to_auth_string()
+            .starts_with("Bearer eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9."));
     }
 }
@@ -29,16 +29,22 @@ impl Uds {
 
     fn read_json(&mut self) -> Result<String, UdsError> {
         let mut buffer = Vec::<u8>::new();
+        let mut delimiter = b'}';
         loop {
             let _read_bytes = self
                 .read_stream
-                .read_until(b'}', &mut buffer)
+                .read_until(delimiter, &mut buffer)
                 .map_err(UdsError::Read)?;
             let utf8_slice = str::from_utf8(&buffer).map_err(UdsError::Utf8)?;
-            if utf8_slice.matches('{').count() == utf8_slice.matches('}').count() {
+            let objects_closed = utf8_slice.matches('{').count() == utf8_slice.matches('}').count();
+            // Batch responses are arrays of objects
+            let arrays_closed = !utf8_slice.trim_start().starts_with('[')
+                || utf8_slice.matches('[').count() == utf8_slice.matches(']').count();
+            if objects_closed && arrays_closed {
                 trace!("Reading from Unix domain socket: {}", utf8_slice);
                 break Ok(utf8_slice.to_string());
             }
+            delimiter = if objects_closed { b']' } else { b'}' };
         }
     }
 
@@ -2,19 +2,30 @@
 //!
 //! This module provides custom types, but also re-exports some types from [ethereum_types].
 
+use crate::utils::keccak256;
+use ethereum_types::FromDecStrErr;
 pub use ethereum_types::{Bloom, H160, H256, H64, U128, U256, U64};
-use serde::de::Visitor;
+use rlp::RlpStream;
+use serde::de::{DeserializeOwned, Visitor};
+use serde::ser::SerializeMap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
 use std::fmt::Debug;
+use std::ops::RangeInclusive;
 use std::str::FromStr;
+use thiserror::Error;
 
 /// Information about block number, defaults to `BlockParameter::Latest`
+///
+/// `Safe` and `Finalized` refer to the blocks marked by the consensus layer after the merge.
 #[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
 pub enum BlockParameter {
     Latest,
     Earliest,
     Pending,
+    Safe,
+    Finalized,
     Custom(U64),
 }
 
@@ -24,6 +35,8 @@ impl Serialize for BlockParameter {
             BlockParameter::Latest => serializer.serialize_str("latest"),
             BlockParameter::Earliest => serializer.serialize_str("earliest"),
             BlockParameter::Pending => serializer.serialize_str("pending"),
+            BlockParameter::Safe => serializer.serialize_str("safe"),
+            BlockParameter::Finalized => serializer.serialize_str("finalized"),
             BlockParameter::Custom(num) => serializer.serialize_str(&format!("{:#x}", num)),
         }
     }
@@ -35,6 +48,47 @@ impl Default for BlockParameter {
     }
 }
 
+/// Selects the block for RPCs which read state, defaults to `BlockParameter::Latest`
+///
+/// Besides a [BlockParameter] the block can be selected by its hash as defined in
+/// [EIP-1898](https://eips.ethereum.org/EIPS/eip-1898). This keeps consecutive state reads
+/// consistent during reorgs. If `require_canonical` is set, the node returns an error if the
+/// block is not part of the canonical chain anymore.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BlockId {
+    Number(BlockParameter),
+    Hash { hash: H256, require_canonical: bool },
+}
+
+impl Serialize for BlockId {
+    fn serialize<T: Serializer>(&self, serializer: T) -> Result<T::Ok, T::Error> {
+        match *self {
+            BlockId::Number(block_param) => block_param.serialize(serializer),
+            BlockId::Hash {
+                hash,
+                require_canonical,
+            } => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("blockHash", &hash)?;
+                map.serialize_entry("requireCanonical", &require_canonical)?;
+                map.end()
+            }
+        }
+    }
+}
+
+impl Default for BlockId {
+    fn default() -> Self {
+        BlockId::Number(BlockParameter::default())
+    }
+}
+
+impl From<BlockParameter> for BlockId {
+    fn from(block_param: BlockParameter) -> Self {
+        BlockId::Number(block_param)
+    }
+}
+
 /// Used for creating transactions
 #[derive(Clone, Debug, PartialEq, Serialize, Default)]
 pub struct TransactionRequest {
@@ -52,6 +106,57 @@ pub struct TransactionRequest {
     pub data: Option<Bytes>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub nonce: Option<U256>,
+    #[serde(rename = "maxFeePerGas")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<U256>,
+    #[serde(rename = "maxPriorityFeePerGas")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<U256>,
+    #[serde(rename = "maxFeePerBlobGas")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_blob_gas: Option<U256>,
+    #[serde(rename = "blobVersionedHashes")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blob_versioned_hashes: Option<Vec<H256>>,
+    #[serde(rename = "accessList")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_list: Option<AccessList>,
+    /// Blobs with their commitments and proofs, which are sent along with the transaction
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sidecar: Option<BlobSidecar>,
+}
+
+impl TransactionRequest {
+    /// Attaches an access list created by [eth_create_access_list](crate::rpc::eth_create_access_list)
+    ///
+    /// Only the access list is taken over, the gas limit is left untouched.
+    pub fn with_access_list(mut self, result: AccessListResult) -> Self {
+        self.access_list = Some(result.access_list);
+        self
+    }
+}
+
+/// Addresses and storage keys a transaction plans to access, as defined in EIP-2930
+pub type AccessList = Vec<AccessListItem>;
+
+/// An entry of an [AccessList]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct AccessListItem {
+    pub address: H160,
+    #[serde(rename = "storageKeys")]
+    pub storage_keys: Vec<H256>,
+}
+
+/// The access list created for a transaction together with the gas used when applying it
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct AccessListResult {
+    #[serde(rename = "accessList")]
+    pub access_list: AccessList,
+    #[serde(rename = "gasUsed")]
+    pub gas_used: U256,
+    /// Set if the transaction reverted
+    pub error: Option<String>,
 }
 
 /// A pending or processed transaction
@@ -75,6 +180,18 @@ pub struct Transaction {
     pub v: Option<U64>,
     pub r: Option<U256>,
     pub s: Option<U256>,
+    #[serde(rename = "type")]
+    pub transaction_type: Option<U64>,
+    #[serde(rename = "maxFeePerGas")]
+    pub max_fee_per_gas: Option<U256>,
+    #[serde(rename = "maxPriorityFeePerGas")]
+    pub max_priority_fee_per_gas: Option<U256>,
+    #[serde(rename = "maxFeePerBlobGas")]
+    pub max_fee_per_blob_gas: Option<U256>,
+    #[serde(rename = "blobVersionedHashes")]
+    pub blob_versioned_hashes: Option<Vec<H256>>,
+    #[serde(rename = "accessList")]
+    pub access_list: Option<AccessList>,
 }
 
 /// Transaction receipt of a processed transaction
@@ -100,6 +217,108 @@ pub struct TransactionReceipt {
     #[serde(rename = "logsBloom")]
     pub logs_bloom: Bloom,
     pub status: U64,
+    #[serde(rename = "blobGasUsed")]
+    pub blob_gas_used: Option<U256>,
+    #[serde(rename = "blobGasPrice")]
+    pub blob_gas_price: Option<U256>,
+}
+
+/// Blobs of an EIP-4844 transaction together with their KZG commitments and proofs
+///
+/// Commitments and proofs are not computed by this library and must be supplied by the caller.
+#[derive(Clone, Debug, PartialEq, Serialize, Default)]
+pub struct BlobSidecar {
+    pub blobs: Vec<Bytes>,
+    pub commitments: Vec<Bytes>,
+    pub proofs: Vec<Bytes>,
+}
+
+impl BlobSidecar {
+    /// Size of a single blob in bytes
+    pub const BLOB_SIZE: usize = 131_072;
+    /// Size of a KZG commitment or proof in bytes
+    pub const KZG_SIZE: usize = 48;
+    const BLOB_TX_TYPE: u8 = 0x03;
+    const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+    /// Checks that blobs, commitments and proofs have matching counts and correct sizes
+    pub fn validate(&self) -> Result<(), BlobError> {
+        if self.blobs.is_empty() {
+            return Err(BlobError::Empty);
+        }
+        if self.commitments.len() != self.blobs.len() || self.proofs.len() != self.blobs.len() {
+            return Err(BlobError::CountMismatch);
+        }
+        let sizes = self
+            .blobs
+            .iter()
+            .map(|blob| (blob, Self::BLOB_SIZE))
+            .chain(self.commitments.iter().map(|com| (com, Self::KZG_SIZE)))
+            .chain(self.proofs.iter().map(|proof| (proof, Self::KZG_SIZE)));
+        for (item, expected) in sizes {
+            if item.0.len() != expected {
+                return Err(BlobError::InvalidSize {
+                    expected,
+                    found: item.0.len(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Computes the versioned hashes of the commitments, which have to be part of the transaction
+    pub fn versioned_hashes(&self) -> Vec<H256> {
+        self.commitments
+            .iter()
+            .map(|commitment| {
+                let mut hash = hmac_sha256::Hash::hash(&commitment.0);
+                hash[0] = Self::VERSIONED_HASH_VERSION_KZG;
+                H256(hash)
+            })
+            .collect()
+    }
+
+    /// Wraps a signed blob transaction into its network representation for
+    /// [eth_send_raw_transaction](crate::rpc::eth_send_raw_transaction)
+    ///
+    /// The signed transaction is expected as `0x03 || rlp(tx_payload_body)`, the result is
+    /// `0x03 || rlp([tx_payload_body, blobs, commitments, proofs])`.
+    pub fn wrap_signed_transaction(&self, signed_transaction: &Bytes) -> Result<Bytes, BlobError> {
+        self.validate()?;
+        let payload_body = match signed_transaction.0.split_first() {
+            Some((&Self::BLOB_TX_TYPE, payload_body)) => payload_body,
+            _ => return Err(BlobError::NoBlobTransaction),
+        };
+        if !rlp::Rlp::new(payload_body).is_list() {
+            return Err(BlobError::NoBlobTransaction);
+        }
+
+        let mut stream = RlpStream::new_list(4);
+        stream.append_raw(payload_body, 1);
+        for items in &[&self.blobs, &self.commitments, &self.proofs] {
+            stream.begin_list(items.len());
+            for item in items.iter() {
+                stream.append(&item.0);
+            }
+        }
+
+        let mut wrapped = vec![Self::BLOB_TX_TYPE];
+        wrapped.extend_from_slice(&stream.out());
+        Ok(Bytes(wrapped))
+    }
+}
+
+/// An error type collecting what can go wrong when building blob sidecars
+#[derive(Debug, Error)]
+pub enum BlobError {
+    #[error("Blob Error: Sidecar contains no blobs")]
+    Empty,
+    #[error("Blob Error: Number of blobs, commitments and proofs differ")]
+    CountMismatch,
+    #[error("Blob Error: Expected {expected} bytes, but found {found}")]
+    InvalidSize { expected: usize, found: usize },
+    #[error("Blob Error: Signed transaction is no blob transaction")]
+    NoBlobTransaction,
 }
 
 ///Contains information about events
@@ -182,6 +401,110 @@ impl<'de> Visitor<'de> for BytesVisitor {
     }
 }
 
+/// An address which is parsed and formatted with mixed-case checksums
+///
+/// Parsing validates the [EIP-55](https://eips.ethereum.org/EIPS/eip-55) checksum of mixed-case
+/// input, while all lowercase or all uppercase input is accepted without checksum. Use
+/// [from_str_with_chain_id](Self::from_str_with_chain_id) and [to_checksum](Self::to_checksum) for
+/// the chain specific checksums of [EIP-1191](https://eips.ethereum.org/EIPS/eip-1191).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct Address(pub H160);
+
+impl Address {
+    /// Parses an address, validating the checksum for `chain_id` if it is mixed-case
+    pub fn from_str_with_chain_id(
+        value: &str,
+        chain_id: Option<u64>,
+    ) -> Result<Self, AddressError> {
+        let hex_address = value.strip_prefix("0x").unwrap_or(value);
+        let mut bytes = [0u8; 20];
+        hex::decode_to_slice(hex_address, &mut bytes)?;
+        let address = Address(H160(bytes));
+
+        let is_lower = !hex_address.chars().any(|c| c.is_ascii_uppercase());
+        let is_upper = !hex_address.chars().any(|c| c.is_ascii_lowercase());
+        if !is_lower && !is_upper && address.to_checksum(chain_id)[2..] != *hex_address {
+            return Err(AddressError::Checksum(value.to_string()));
+        }
+        Ok(address)
+    }
+
+    /// Formats the address with checksum, which includes `chain_id` as defined in EIP-1191
+    pub fn to_checksum(&self, chain_id: Option<u64>) -> String {
+        let hex_address = hex::encode(self.0.as_bytes());
+        let hash_input = match chain_id {
+            Some(chain_id) => format!("{}0x{}", chain_id, hex_address),
+            None => hex_address.clone(),
+        };
+        let hash = keccak256(hash_input.as_bytes());
+
+        let checksummed: String = hex_address
+            .chars()
+            .enumerate()
+            .map(|(index, c)| {
+                let nibble = (hash[index / 2] >> (4 * (1 - index % 2))) & 0x0f;
+                if nibble >= 8 {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
+                }
+            })
+            .collect();
+        String::from("0x") + &checksummed
+    }
+}
+
+impl FromStr for Address {
+    type Err = AddressError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::from_str_with_chain_id(value, None)
+    }
+}
+
+impl std::fmt::Display for Address {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_checksum(None))
+    }
+}
+
+impl From<H160> for Address {
+    fn from(address: H160) -> Self {
+        Address(address)
+    }
+}
+
+impl From<Address> for H160 {
+    fn from(address: Address) -> Self {
+        address.0
+    }
+}
+
+impl Serialize for Address {
+    fn serialize<T: Serializer>(&self, serializer: T) -> Result<T::Ok, T::Error> {
+        serializer.serialize_str(&self.to_checksum(None))
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<T>(deserializer: T) -> Result<Address, T::Error>
+    where
+        T: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        Address::from_str(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+/// An error type collecting what can go wrong when parsing an [Address]
+#[derive(Debug, Error)]
+pub enum AddressError {
+    #[error("Address Error: No 20 byte hex string: {0}")]
+    Hex(#[from] hex::FromHexError),
+    #[error("Address Error: Invalid checksum of {0}")]
+    Checksum(String),
+}
+
 /// Wrapper for private keys to allow for 0x-prefixed and plain serialization
 #[derive(Clone, Debug, PartialEq)]
 pub enum PrivateKey {
@@ -198,6 +521,108 @@ impl Serialize for PrivateKey {
     }
 }
 
+/// An ECDSA signature over secp256k1, like the ones returned by
+/// [eth_sign](crate::rpc::eth_sign) and [personal_sign](crate::rpc::personal_sign)
+///
+/// `v` is kept as it was found, i.e. as recovery id `0` or `1`, as `27` or `28` or as EIP-155 value.
+/// Signatures are read from and converted to 65 bytes `r || s || v`, and the 64 bytes compact
+/// representation of [EIP-2098](https://eips.ethereum.org/EIPS/eip-2098) is supported as well.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Signature {
+    pub r: H256,
+    pub s: H256,
+    pub v: u64,
+}
+
+impl Signature {
+    /// Parses a 65 bytes signature, or a 64 bytes compact signature which yields `v` of 27 or 28
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SignatureError> {
+        match bytes.len() {
+            65 => Ok(Signature {
+                r: H256::from_slice(&bytes[..32]),
+                s: H256::from_slice(&bytes[32..64]),
+                v: bytes[64] as u64,
+            }),
+            64 => {
+                let mut s = H256::from_slice(&bytes[32..]);
+                let y_parity = s.0[0] >> 7;
+                s.0[0] &= 0x7f;
+                Ok(Signature {
+                    r: H256::from_slice(&bytes[..32]),
+                    s,
+                    v: 27 + y_parity as u64,
+                })
+            }
+            len => Err(SignatureError::Length(len)),
+        }
+    }
+
+    /// Returns `0` or `1`, the parity of the y coordinate of the point `r`
+    pub fn recovery_id(&self) -> Result<u8, SignatureError> {
+        match self.v {
+            0 | 1 => Ok(self.v as u8),
+            27 | 28 => Ok((self.v - 27) as u8),
+            v if v >= 35 => Ok(((v - 35) % 2) as u8),
+            v => Err(SignatureError::RecoveryId(v)),
+        }
+    }
+
+    /// Converts to 65 bytes `r || s || v`, with `v` normalized to 27 or 28
+    pub fn to_bytes(&self) -> Result<Bytes, SignatureError> {
+        let mut bytes = self.r.as_bytes().to_vec();
+        bytes.extend_from_slice(self.s.as_bytes());
+        bytes.push(27 + self.recovery_id()?);
+        Ok(Bytes(bytes))
+    }
+
+    /// Converts to 64 bytes `r || yParityAndS` as defined in EIP-2098
+    pub fn to_compact(&self) -> Result<Bytes, SignatureError> {
+        let mut y_parity_and_s = self.s;
+        y_parity_and_s.0[0] |= self.recovery_id()? << 7;
+        let mut bytes = self.r.as_bytes().to_vec();
+        bytes.extend_from_slice(y_parity_and_s.as_bytes());
+        Ok(Bytes(bytes))
+    }
+}
+
+impl TryFrom<&Bytes> for Signature {
+    type Error = SignatureError;
+
+    fn try_from(bytes: &Bytes) -> Result<Self, Self::Error> {
+        Signature::from_bytes(&bytes.0)
+    }
+}
+
+/// An error type collecting what can go wrong when reading a [Signature]
+#[derive(Debug, Error, PartialEq)]
+pub enum SignatureError {
+    #[error("Signature Error: Expected 64 or 65 bytes, found {0}")]
+    Length(usize),
+    #[error("Signature Error: Invalid v {0}")]
+    RecoveryId(u64),
+}
+
+/// Structured data to be signed as defined in [EIP-712](https://eips.ethereum.org/EIPS/eip-712)
+///
+/// This is the JSON format used by wallets and [eth_sign_typed_data_v4](crate::rpc::eth_sign_typed_data_v4).
+/// `domain` and `message` are kept as JSON and are interpreted according to `types`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TypedData {
+    pub types: BTreeMap<String, Vec<TypedDataField>>,
+    #[serde(rename = "primaryType")]
+    pub primary_type: String,
+    pub domain: serde_json::Value,
+    pub message: serde_json::Value,
+}
+
+/// A member of a struct type of [TypedData]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TypedDataField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub field_type: String,
+}
+
 /// Standard block type
 #[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct Block {
@@ -217,9 +642,11 @@ pub struct Block {
     #[serde(rename = "receiptsRoot")]
     pub receipts_root: H256,
     pub miner: H160,
+    /// Zero after the merge
     pub difficulty: U256,
+    /// Dropped by clients after the merge
     #[serde(rename = "totalDifficulty")]
-    pub total_difficulty: U256,
+    pub total_difficulty: Option<U256>,
     #[serde(rename = "extraData")]
     pub extra_data: Bytes,
     pub size: U256,
@@ -230,6 +657,39 @@ pub struct Block {
     pub timestamp: U256,
     pub transactions: Vec<TransactionOrHash>,
     pub uncles: Vec<H256>,
+    /// Validator withdrawals since Shanghai
+    pub withdrawals: Option<Vec<Withdrawal>>,
+    #[serde(rename = "mixHash")]
+    pub mix_hash: Option<H256>,
+    #[serde(rename = "baseFeePerGas")]
+    pub base_fee_per_gas: Option<U256>,
+    #[serde(rename = "withdrawalsRoot")]
+    pub withdrawals_root: Option<H256>,
+    #[serde(rename = "blobGasUsed")]
+    pub blob_gas_used: Option<U256>,
+    #[serde(rename = "excessBlobGas")]
+    pub excess_blob_gas: Option<U256>,
+    #[serde(rename = "parentBeaconBlockRoot")]
+    pub parent_beacon_block_root: Option<H256>,
+    #[serde(rename = "requestsHash")]
+    pub requests_hash: Option<H256>,
+}
+
+impl Block {
+    /// Checks if the block was produced by proof of stake
+    pub fn is_post_merge(&self) -> bool {
+        self.difficulty.is_zero()
+    }
+
+    /// Recomputes the block hash from the header fields, c.f. [BlockHeader::compute_hash]
+    pub fn compute_hash(&self) -> Result<H256, BlockHashError> {
+        BlockHeader::from(self).compute_hash()
+    }
+
+    /// Checks that the returned hash matches the hash of the header fields
+    pub fn verify_hash(&self) -> Result<(), BlockHashError> {
+        BlockHeader::from(self).verify_hash()
+    }
 }
 
 /// BlockHeader returned by subscription
@@ -258,8 +718,113 @@ pub struct BlockHeader {
     pub gas_used: U256,
     #[serde(rename = "extraData")]
     pub extra_data: Bytes,
+    pub timestamp: U256,
     #[serde(rename = "mixHash")]
     pub mix_hash: Option<H256>,
+    #[serde(rename = "baseFeePerGas")]
+    pub base_fee_per_gas: Option<U256>,
+    #[serde(rename = "withdrawalsRoot")]
+    pub withdrawals_root: Option<H256>,
+    #[serde(rename = "blobGasUsed")]
+    pub blob_gas_used: Option<U256>,
+    #[serde(rename = "excessBlobGas")]
+    pub excess_blob_gas: Option<U256>,
+    #[serde(rename = "parentBeaconBlockRoot")]
+    pub parent_beacon_block_root: Option<H256>,
+    #[serde(rename = "requestsHash")]
+    pub requests_hash: Option<H256>,
+}
+
+impl BlockHeader {
+    /// Recomputes the block hash as keccak hash of the RLP encoded header fields
+    ///
+    /// Fields which were added by later hard forks are appended as long as they are present.
+    /// Pending blocks cannot be hashed, because they lack some of the required fields.
+    pub fn compute_hash(&self) -> Result<H256, BlockHashError> {
+        let missing = BlockHashError::MissingField;
+        let mut stream = RlpStream::new();
+        stream.begin_unbounded_list();
+        stream
+            .append(&self.parent_hash)
+            .append(&self.sha3_uncles)
+            .append(&self.miner)
+            .append(&self.state_root)
+            .append(&self.transactions_root)
+            .append(&self.receipts_root)
+            .append(&self.logs_bloom.ok_or(missing("logsBloom"))?)
+            .append(&self.difficulty)
+            .append(&self.number.ok_or(missing("number"))?)
+            .append(&self.gas_limit)
+            .append(&self.gas_used)
+            .append(&self.timestamp)
+            .append(&self.extra_data.0)
+            .append(&self.mix_hash.ok_or(missing("mixHash"))?)
+            .append(&self.nonce.ok_or(missing("nonce"))?);
+
+        // Each hard fork only appends fields, so stop at the first one which is missing
+        let fork_fields = [
+            self.base_fee_per_gas.map(|fee| rlp::encode(&fee)),
+            self.withdrawals_root.map(|root| rlp::encode(&root)),
+            self.blob_gas_used.map(|gas| rlp::encode(&gas)),
+            self.excess_blob_gas.map(|gas| rlp::encode(&gas)),
+            self.parent_beacon_block_root.map(|root| rlp::encode(&root)),
+            self.requests_hash.map(|hash| rlp::encode(&hash)),
+        ];
+        for field in fork_fields.iter().map_while(Option::as_ref) {
+            stream.append_raw(field, 1);
+        }
+        stream.finalize_unbounded_list();
+        Ok(H256(keccak256(&stream.out())))
+    }
+
+    /// Checks that the returned hash matches the hash of the header fields
+    pub fn verify_hash(&self) -> Result<(), BlockHashError> {
+        let expected = self.hash.ok_or(BlockHashError::MissingField("hash"))?;
+        let computed = self.compute_hash()?;
+        if expected == computed {
+            Ok(())
+        } else {
+            Err(BlockHashError::Mismatch { expected, computed })
+        }
+    }
+}
+
+impl From<&Block> for BlockHeader {
+    fn from(block: &Block) -> Self {
+        BlockHeader {
+            number: block.number,
+            hash: block.hash,
+            parent_hash: block.parent_hash,
+            nonce: block.nonce,
+            transactions_root: block.transactions_root,
+            state_root: block.state_root,
+            receipts_root: block.receipts_root,
+            difficulty: block.difficulty,
+            sha3_uncles: block.sha3_uncles,
+            miner: block.miner,
+            logs_bloom: block.logs_bloom,
+            gas_limit: block.gas_limit,
+            gas_used: block.gas_used,
+            extra_data: block.extra_data.clone(),
+            timestamp: block.timestamp,
+            mix_hash: block.mix_hash,
+            base_fee_per_gas: block.base_fee_per_gas,
+            withdrawals_root: block.withdrawals_root,
+            blob_gas_used: block.blob_gas_used,
+            excess_blob_gas: block.excess_blob_gas,
+            parent_beacon_block_root: block.parent_beacon_block_root,
+            requests_hash: block.requests_hash,
+        }
+    }
+}
+
+/// An error type collecting what can go wrong when verifying block hashes
+#[derive(Debug, Error)]
+pub enum BlockHashError {
+    #[error("Block Hash Error: Header field {0} is missing")]
+    MissingField(&'static str),
+    #[error("Block Hash Error: Expected {expected:?}, but header hashes to {computed:?}")]
+    Mismatch { expected: H256, computed: H256 },
 }
 
 /// Wrapper to allow returned blocks to contain complete transactions or hashes
@@ -305,6 +870,54 @@ pub struct GasCall {
     pub data: Option<Bytes>,
 }
 
+/// Overrides of account state for simulating calls, keyed by account address
+pub type StateOverride = HashMap<H160, AccountOverride>;
+
+/// Replaces parts of an account for the duration of a call
+///
+/// `state` replaces the whole storage of the account, while `state_diff` only replaces the
+/// given slots. Only one of them can be set.
+#[derive(Clone, Debug, PartialEq, Serialize, Default)]
+pub struct AccountOverride {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<U64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<HashMap<H256, H256>>,
+    #[serde(rename = "stateDiff")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<HashMap<H256, H256>>,
+}
+
+/// Replaces fields of the block a call is executed in
+#[derive(Clone, Debug, PartialEq, Serialize, Default)]
+pub struct BlockOverrides {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number: Option<U64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<U64>,
+    #[serde(rename = "gasLimit")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<U64>,
+    #[serde(rename = "feeRecipient")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_recipient: Option<H160>,
+    #[serde(rename = "prevRandao")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_randao: Option<H256>,
+    #[serde(rename = "baseFeePerGas")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_fee_per_gas: Option<U256>,
+    #[serde(rename = "blobBaseFee")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blob_base_fee: Option<U256>,
+}
+
 /// A filter object to listen for events
 #[derive(Clone, Debug, PartialEq, Serialize, Default)]
 pub struct Filter {
@@ -354,90 +967,831 @@ pub struct TxPoolStatus {
 }
 
 /// Content of the transaction pool
+///
+/// Transactions are grouped by sender and keyed by their nonce.
 #[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct TxPoolContent {
-    pub pending: HashMap<H160, HashMap<String, Transaction>>,
-    pub queued: HashMap<H160, HashMap<String, Transaction>>,
+    #[serde(deserialize_with = "deserialize_nonce_map")]
+    pub pending: HashMap<H160, BTreeMap<U256, Transaction>>,
+    #[serde(deserialize_with = "deserialize_nonce_map")]
+    pub queued: HashMap<H160, BTreeMap<U256, Transaction>>,
+}
+
+impl TxPoolContent {
+    /// Pending transactions of `sender`, ordered by nonce
+    pub fn pending_by_nonce(&self, sender: &H160) -> Vec<&Transaction> {
+        self.pending
+            .get(sender)
+            .map(|txs| txs.values().collect())
+            .unwrap_or_default()
+    }
+
+    /// Queued transactions of `sender`, ordered by nonce
+    pub fn queued_by_nonce(&self, sender: &H160) -> Vec<&Transaction> {
+        self.queued
+            .get(sender)
+            .map(|txs| txs.values().collect())
+            .unwrap_or_default()
+    }
+
+    /// Ranges of nonces of `sender` which are missing between its lowest and highest nonce in
+    /// the pool.
+    ///
+    /// Pending and queued transactions are considered together, so a non-empty result
+    /// explains why transactions are stuck in the queue.
+    pub fn nonce_gaps(&self, sender: &H160) -> Vec<RangeInclusive<U256>> {
+        let mut nonces = self
+            .pending
+            .get(sender)
+            .into_iter()
+            .chain(self.queued.get(sender))
+            .flat_map(|txs| txs.keys().copied())
+            .collect::<Vec<U256>>();
+        nonces.sort();
+        nonces.dedup();
+
+        nonces
+            .windows(2)
+            .filter(|pair| pair[1] - pair[0] > U256::one())
+            .map(|pair| pair[0] + 1..=pair[1] - 1)
+            .collect()
+    }
+
+    /// Sums up value and gas of all pending and queued transactions of `sender`
+    pub fn totals(&self, sender: &H160) -> TxPoolTotals {
+        self.pending
+            .get(sender)
+            .into_iter()
+            .chain(self.queued.get(sender))
+            .flat_map(|txs| txs.values())
+            .fold(TxPoolTotals::default(), |mut totals, tx| {
+                totals.count += 1;
+                totals.value = totals.value.saturating_add(tx.value);
+                totals.gas = totals.gas.saturating_add(tx.gas);
+                totals.fees = totals
+                    .fees
+                    .saturating_add(tx.gas.saturating_mul(tx.gas_price));
+                totals
+            })
+    }
+}
+
+/// Aggregated values of transactions in the transaction pool
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct TxPoolTotals {
+    /// Number of transactions
+    pub count: usize,
+    /// Sum of transferred value in wei
+    pub value: U256,
+    /// Sum of gas limits
+    pub gas: U256,
+    /// Sum of `gas * gas_price` in wei, i.e. the maximum fees which can be paid
+    pub fees: U256,
 }
 
 /// Content of transaction pool in debug view
+///
+/// Summaries are grouped by sender and keyed by their nonce. Use [TxPoolSummary] to parse them.
 #[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct TxPoolInspect {
-    pub pending: HashMap<H160, HashMap<String, String>>,
-    pub queued: HashMap<H160, HashMap<String, String>>,
+    #[serde(deserialize_with = "deserialize_nonce_map")]
+    pub pending: HashMap<H160, BTreeMap<U256, String>>,
+    #[serde(deserialize_with = "deserialize_nonce_map")]
+    pub queued: HashMap<H160, BTreeMap<U256, String>>,
 }
 
-/// Wrapper for node synchronization info
-#[derive(Clone, Debug, PartialEq, Deserialize)]
-#[serde(untagged)]
-pub enum SyncInfo {
-    Syncing(SyncStatus),
-    NotSyncing(bool),
+/// A parsed transaction summary of [TxPoolInspect]
+///
+/// Geth formats these as `"to: value wei + gas × gasPrice wei"`, where `to` is
+/// `contract creation` for deployments.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TxPoolSummary {
+    pub to: Option<H160>,
+    pub value: U256,
+    pub gas: U256,
+    pub gas_price: U256,
 }
 
-/// Wrapper for node synchronization info in a subscription
-#[derive(Clone, Debug, PartialEq, Deserialize)]
-pub struct SyncInfoSubscription {
-    pub syncing: bool,
-    pub status: Option<SyncStatus>,
+impl FromStr for TxPoolSummary {
+    type Err = TxPoolSummaryError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let malformed = || TxPoolSummaryError::Malformed(value.to_string());
+
+        let (to, amounts) = value.split_once(": ").ok_or_else(malformed)?;
+        let (tx_value, gas_amounts) = amounts.split_once(" wei + ").ok_or_else(malformed)?;
+        let (gas, gas_price) = gas_amounts.split_once(" gas × ").ok_or_else(malformed)?;
+        let gas_price = gas_price.strip_suffix(" wei").ok_or_else(malformed)?;
+
+        let to = match to {
+            "contract creation" => None,
+            address => Some(
+                H160::from_str(address)
+                    .map_err(|_| TxPoolSummaryError::Address(address.to_string()))?,
+            ),
+        };
+
+        Ok(TxPoolSummary {
+            to,
+            value: U256::from_dec_str(tx_value)?,
+            gas: U256::from_dec_str(gas)?,
+            gas_price: U256::from_dec_str(gas_price)?,
+        })
+    }
 }
 
-/// Status object containing information about node synchronization
-#[derive(Clone, Debug, PartialEq, Deserialize)]
-pub struct SyncStatus {
-    #[serde(rename = "startingBlock")]
-    pub starting_block: u64,
-    #[serde(rename = "currentBlock")]
-    pub current_block: u64,
-    #[serde(rename = "highestBlock")]
-    pub highest_block: u64,
-    #[serde(rename = "pulledStates")]
-    pub pulled_states: Option<u64>,
-    #[serde(rename = "knownStates")]
-    pub known_states: Option<u64>,
+/// An error type collecting what can go wrong when parsing a [TxPoolSummary]
+#[derive(Debug, Error)]
+pub enum TxPoolSummaryError {
+    #[error("TxPool Summary Error: Unexpected format {0:?}")]
+    Malformed(String),
+    #[error("TxPool Summary Error: Invalid address {0}")]
+    Address(String),
+    #[error("TxPool Summary Error: Invalid number: {0:?}")]
+    Number(#[from] FromDecStrErr),
 }
 
-/// A wrapper for a signed transaction
+/// Content of the transaction pool for a single sender, keyed by nonce
 #[derive(Clone, Debug, PartialEq, Deserialize)]
-pub struct SignedTransaction {
-    pub raw: Bytes,
-    pub tx: Transaction,
+pub struct TxPoolContentFrom {
+    #[serde(deserialize_with = "deserialize_nonces")]
+    pub pending: BTreeMap<U256, Transaction>,
+    #[serde(deserialize_with = "deserialize_nonces")]
+    pub queued: BTreeMap<U256, Transaction>,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+// Nodes return the nonces as decimal strings, which cannot be deserialized into U256 directly
+fn deserialize_nonces<'de, D, T>(deserializer: D) -> Result<BTreeMap<U256, T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let raw = HashMap::<String, T>::deserialize(deserializer)?;
+    parse_nonces(raw)
+}
 
-    #[test]
-    fn test_types_bytes() {
-        let bytes_0 = Bytes::from_slice(&[]);
-        let bytes_1 = Bytes::from_slice(&[0, 0]);
-        let bytes_2 = Bytes::from_slice(&[17, 234]);
-        let bytes_3 = Bytes::from_str("0x").unwrap();
-        let bytes_4 = Bytes::from_str("0x00").unwrap();
-        let bytes_5 = Bytes::from_str("00421100").unwrap();
+fn deserialize_nonce_map<'de, D, T>(
+    deserializer: D,
+) -> Result<HashMap<H160, BTreeMap<U256, T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let raw = HashMap::<H160, HashMap<String, T>>::deserialize(deserializer)?;
+    raw.into_iter()
+        .map(|(sender, txs)| Ok((sender, parse_nonces(txs)?)))
+        .collect()
+}
 
-        let expected_0 = "\"0x\"";
-        let expected_1 = "\"0x0000\"";
-        let expected_2 = "\"0x11ea\"";
-        let expected_3 = Bytes(vec![]);
-        let expected_4 = Bytes(vec![0]);
-        let expected_5 = Bytes(vec![0, 66, 17, 0]);
+fn parse_nonces<T, E: serde::de::Error>(raw: HashMap<String, T>) -> Result<BTreeMap<U256, T>, E> {
+    raw.into_iter()
+        .map(|(nonce, tx)| {
+            U256::from_dec_str(&nonce)
+                .map(|parsed| (parsed, tx))
+                .map_err(|err| E::custom(format!("Invalid nonce {}: {:?}", nonce, err)))
+        })
+        .collect()
+}
 
-        assert_eq!(serde_json::to_string(&bytes_0).unwrap(), expected_0);
-        assert_eq!(serde_json::to_string(&bytes_1).unwrap(), expected_1);
-        assert_eq!(serde_json::to_string(&bytes_2).unwrap(), expected_2);
-        assert_eq!(bytes_3, expected_3);
-        assert_eq!(bytes_4, expected_4);
-        assert_eq!(bytes_5, expected_5);
-    }
+/// Options for the `debug_trace*` RPCs
+///
+/// If no [tracer](Self::tracer) is set, the default struct logger is used.
+#[derive(Clone, Debug, PartialEq, Serialize, Default)]
+pub struct TraceOptions {
+    #[serde(rename = "disableStorage")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_storage: Option<bool>,
+    #[serde(rename = "disableStack")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_stack: Option<bool>,
+    /// Used by older nodes, newer ones capture no memory by default
+    #[serde(rename = "disableMemory")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_memory: Option<bool>,
+    #[serde(rename = "enableMemory")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_memory: Option<bool>,
+    #[serde(rename = "enableReturnData")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_return_data: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tracer: Option<Tracer>,
+    /// Tracer specific configuration, e.g. `{"onlyTopCall": true}` for the call tracer
+    #[serde(rename = "tracerConfig")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tracer_config: Option<serde_json::Value>,
+    /// A duration string like `"10s"`, defaults to 5 seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+}
 
-    #[test]
-    fn test_types_block_parameter() {
-        let block_param_default = BlockParameter::default();
-        let block_param_custom = BlockParameter::Custom(U64::from(11827902));
+/// Selects one of the built-in tracers or a custom javascript tracer
+#[derive(Clone, Debug, PartialEq)]
+pub enum Tracer {
+    CallTracer,
+    PrestateTracer,
+    Custom(String),
+}
 
-        assert_eq!(
+impl Serialize for Tracer {
+    fn serialize<T: Serializer>(&self, serializer: T) -> Result<T::Ok, T::Error> {
+        match self {
+            Tracer::CallTracer => serializer.serialize_str("callTracer"),
+            Tracer::PrestateTracer => serializer.serialize_str("prestateTracer"),
+            Tracer::Custom(tracer) => serializer.serialize_str(tracer),
+        }
+    }
+}
+
+/// Result of a `debug_trace*` RPC, depending on the chosen [Tracer]
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum TraceResult {
+    StructLogs(StructLogTrace),
+    CallFrame(CallFrame),
+    PrestateDiff(PrestateDiff),
+    Prestate(HashMap<H160, PrestateAccount>),
+    /// Output of custom tracers
+    Custom(serde_json::Value),
+}
+
+/// Trace result of the default struct logger
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct StructLogTrace {
+    pub gas: u64,
+    pub failed: bool,
+    #[serde(rename = "returnValue")]
+    pub return_value: Bytes,
+    #[serde(rename = "structLogs")]
+    pub struct_logs: Vec<StructLog>,
+}
+
+/// A single executed opcode
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct StructLog {
+    pub pc: u64,
+    pub op: String,
+    pub gas: u64,
+    #[serde(rename = "gasCost")]
+    pub gas_cost: u64,
+    pub depth: u64,
+    pub error: Option<String>,
+    pub stack: Option<Vec<U256>>,
+    pub memory: Option<Vec<Bytes>>,
+    pub storage: Option<HashMap<H256, H256>>,
+    #[serde(rename = "returnData")]
+    pub return_data: Option<Bytes>,
+}
+
+/// A call frame returned by the call tracer, containing its nested calls
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct CallFrame {
+    #[serde(rename = "type")]
+    pub call_type: CallType,
+    pub from: H160,
+    pub to: Option<H160>,
+    pub value: Option<U256>,
+    pub gas: U256,
+    #[serde(rename = "gasUsed")]
+    pub gas_used: U256,
+    pub input: Bytes,
+    pub output: Option<Bytes>,
+    pub error: Option<String>,
+    #[serde(rename = "revertReason")]
+    pub revert_reason: Option<String>,
+    #[serde(default)]
+    pub calls: Vec<CallFrame>,
+}
+
+/// The kind of a [CallFrame] or [CallAction]
+///
+/// Geth uses upper case names, while OpenEthereum style traces use lower case names.
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum CallType {
+    #[serde(alias = "call")]
+    Call,
+    #[serde(alias = "staticcall")]
+    StaticCall,
+    #[serde(alias = "delegatecall")]
+    DelegateCall,
+    #[serde(alias = "callcode")]
+    CallCode,
+    Create,
+    Create2,
+    SelfDestruct,
+}
+
+/// Account state returned by the prestate tracer
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct PrestateAccount {
+    pub balance: Option<U256>,
+    pub nonce: Option<u64>,
+    pub code: Option<Bytes>,
+    pub storage: Option<HashMap<H256, H256>>,
+}
+
+/// Returned by the prestate tracer in `diffMode`
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct PrestateDiff {
+    pub pre: HashMap<H160, PrestateAccount>,
+    pub post: HashMap<H160, PrestateAccount>,
+}
+
+/// Trace of a single transaction when tracing whole blocks
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct BlockTrace {
+    #[serde(rename = "txHash")]
+    pub tx_hash: Option<H256>,
+    pub result: Option<TraceResult>,
+    pub error: Option<String>,
+}
+
+/// The kinds of traces which can be requested by `trace_call` and `trace_replayTransaction`
+#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TraceType {
+    Trace,
+    VmTrace,
+    StateDiff,
+}
+
+/// Filter object for `trace_filter`
+#[derive(Clone, Debug, PartialEq, Serialize, Default)]
+pub struct TraceFilter {
+    #[serde(rename = "fromBlock")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_block: Option<BlockParameter>,
+    #[serde(rename = "toBlock")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_block: Option<BlockParameter>,
+    #[serde(rename = "fromAddress")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_address: Option<Vec<H160>>,
+    #[serde(rename = "toAddress")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_address: Option<Vec<H160>>,
+    /// Offset of the first returned trace
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<u64>,
+    /// Maximum number of returned traces
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u64>,
+}
+
+/// A trace with information about its position in the chain
+///
+/// Returned by `trace_block`, `trace_transaction` and `trace_filter`.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct LocalizedTrace {
+    #[serde(flatten)]
+    pub action: Action,
+    pub result: Option<ActionResult>,
+    pub error: Option<String>,
+    #[serde(rename = "traceAddress")]
+    pub trace_address: Vec<usize>,
+    pub subtraces: usize,
+    #[serde(rename = "transactionPosition")]
+    pub transaction_position: Option<u64>,
+    #[serde(rename = "transactionHash")]
+    pub transaction_hash: Option<H256>,
+    #[serde(rename = "blockNumber")]
+    pub block_number: u64,
+    #[serde(rename = "blockHash")]
+    pub block_hash: H256,
+}
+
+/// A trace as part of [TraceResults]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct Trace {
+    #[serde(flatten)]
+    pub action: Action,
+    pub result: Option<ActionResult>,
+    pub error: Option<String>,
+    #[serde(rename = "traceAddress")]
+    pub trace_address: Vec<usize>,
+    pub subtraces: usize,
+}
+
+/// The action which has been traced
+///
+/// The variant is selected by the `type` field of the trace and deserialized from its `action`
+/// field.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(tag = "type", content = "action", rename_all = "lowercase")]
+pub enum Action {
+    Call(CallAction),
+    Create(CreateAction),
+    Suicide(SuicideAction),
+    Reward(RewardAction),
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct CallAction {
+    pub from: H160,
+    pub to: H160,
+    pub value: U256,
+    pub gas: U256,
+    pub input: Bytes,
+    #[serde(rename = "callType")]
+    pub call_type: CallType,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct CreateAction {
+    pub from: H160,
+    pub value: U256,
+    pub gas: U256,
+    pub init: Bytes,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct SuicideAction {
+    pub address: H160,
+    #[serde(rename = "refundAddress")]
+    pub refund_address: H160,
+    pub balance: U256,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct RewardAction {
+    pub author: H160,
+    pub value: U256,
+    #[serde(rename = "rewardType")]
+    pub reward_type: RewardType,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RewardType {
+    Block,
+    Uncle,
+    EmptyStep,
+    External,
+}
+
+/// The outcome of a traced action, which is missing for failed actions
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum ActionResult {
+    Create(CreateResult),
+    Call(CallResult),
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct CallResult {
+    #[serde(rename = "gasUsed")]
+    pub gas_used: U256,
+    pub output: Bytes,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct CreateResult {
+    #[serde(rename = "gasUsed")]
+    pub gas_used: U256,
+    pub code: Bytes,
+    pub address: H160,
+}
+
+/// Result of `trace_call` and `trace_replayTransaction`
+///
+/// Only the requested [trace types](TraceType) are present.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct TraceResults {
+    pub output: Bytes,
+    pub trace: Option<Vec<Trace>>,
+    #[serde(rename = "vmTrace")]
+    pub vm_trace: Option<VmTrace>,
+    #[serde(rename = "stateDiff")]
+    pub state_diff: Option<HashMap<H160, AccountDiff>>,
+    #[serde(rename = "transactionHash")]
+    pub transaction_hash: Option<H256>,
+}
+
+/// A virtual machine trace of executed code
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct VmTrace {
+    pub code: Bytes,
+    pub ops: Vec<VmOperation>,
+}
+
+/// A single instruction of a [VmTrace]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct VmOperation {
+    pub pc: usize,
+    pub cost: u64,
+    pub ex: Option<VmExecutedOperation>,
+    /// The trace of a sub call or contract creation
+    pub sub: Option<VmTrace>,
+}
+
+/// Effects of an executed instruction
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct VmExecutedOperation {
+    pub used: u64,
+    pub push: Vec<U256>,
+    pub mem: Option<MemoryDiff>,
+    pub store: Option<StorageDiff>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct MemoryDiff {
+    pub off: usize,
+    pub data: Bytes,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct StorageDiff {
+    pub key: U256,
+    pub val: U256,
+}
+
+/// Changes of an account caused by a transaction
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct AccountDiff {
+    pub balance: Delta<U256>,
+    pub nonce: Delta<U256>,
+    pub code: Delta<Bytes>,
+    pub storage: HashMap<H256, Delta<H256>>,
+}
+
+/// Describes how a value changed in a [state diff](AccountDiff)
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub enum Delta<T> {
+    #[serde(rename = "=")]
+    Unchanged,
+    #[serde(rename = "+")]
+    Added(T),
+    #[serde(rename = "-")]
+    Removed(T),
+    #[serde(rename = "*")]
+    Changed(ChangedValue<T>),
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct ChangedValue<T> {
+    pub from: T,
+    pub to: T,
+}
+
+/// Information about the running node, returned by `admin_nodeInfo`
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct NodeInfo {
+    pub id: String,
+    pub name: String,
+    pub enode: String,
+    pub enr: Option<String>,
+    pub ip: String,
+    pub ports: NodePorts,
+    #[serde(rename = "listenAddr")]
+    pub listen_addr: String,
+    /// Protocol specific information, e.g. about the `eth` protocol
+    pub protocols: HashMap<String, serde_json::Value>,
+}
+
+/// Network ports of a [NodeInfo]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct NodePorts {
+    pub discovery: u16,
+    pub listener: u16,
+}
+
+/// Information about a connected peer, returned by `admin_peers`
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct PeerInfo {
+    pub id: String,
+    pub name: String,
+    pub enode: Option<String>,
+    pub enr: Option<String>,
+    pub caps: Vec<String>,
+    pub network: PeerNetwork,
+    /// Protocol specific information, e.g. about the `eth` protocol
+    pub protocols: HashMap<String, serde_json::Value>,
+}
+
+/// Connection details of a [PeerInfo]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct PeerNetwork {
+    #[serde(rename = "localAddress")]
+    pub local_address: String,
+    #[serde(rename = "remoteAddress")]
+    pub remote_address: String,
+    pub inbound: bool,
+    pub trusted: bool,
+    #[serde(rename = "static")]
+    pub static_node: bool,
+}
+
+/// Account and storage proofs returned by `eth_getProof`
+///
+/// Use the [proof](crate::proof) module to verify them against a state root.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct AccountProof {
+    pub address: H160,
+    #[serde(rename = "accountProof")]
+    pub account_proof: Vec<Bytes>,
+    pub balance: U256,
+    #[serde(rename = "codeHash")]
+    pub code_hash: H256,
+    pub nonce: U256,
+    #[serde(rename = "storageHash")]
+    pub storage_hash: H256,
+    #[serde(rename = "storageProof")]
+    pub storage_proof: Vec<StorageProof>,
+}
+
+/// Proof for a single storage slot of an [AccountProof]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct StorageProof {
+    pub key: U256,
+    pub value: U256,
+    pub proof: Vec<Bytes>,
+}
+
+/// A validator withdrawal from the beacon chain
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Withdrawal {
+    pub index: U64,
+    #[serde(rename = "validatorIndex")]
+    pub validator_index: U64,
+    pub address: H160,
+    /// The withdrawn amount in gwei
+    pub amount: U64,
+}
+
+/// An execution payload of the Engine API
+///
+/// `withdrawals` is only used from `V2` on.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionPayload {
+    #[serde(rename = "parentHash")]
+    pub parent_hash: H256,
+    #[serde(rename = "feeRecipient")]
+    pub fee_recipient: H160,
+    #[serde(rename = "stateRoot")]
+    pub state_root: H256,
+    #[serde(rename = "receiptsRoot")]
+    pub receipts_root: H256,
+    #[serde(rename = "logsBloom")]
+    pub logs_bloom: Bloom,
+    #[serde(rename = "prevRandao")]
+    pub prev_randao: H256,
+    #[serde(rename = "blockNumber")]
+    pub block_number: U64,
+    #[serde(rename = "gasLimit")]
+    pub gas_limit: U64,
+    #[serde(rename = "gasUsed")]
+    pub gas_used: U64,
+    pub timestamp: U64,
+    #[serde(rename = "extraData")]
+    pub extra_data: Bytes,
+    #[serde(rename = "baseFeePerGas")]
+    pub base_fee_per_gas: U256,
+    #[serde(rename = "blockHash")]
+    pub block_hash: H256,
+    /// RLP encoded transactions
+    pub transactions: Vec<Bytes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub withdrawals: Option<Vec<Withdrawal>>,
+}
+
+/// Result of `engine_getPayloadV2`
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct ExecutionPayloadEnvelope {
+    #[serde(rename = "executionPayload")]
+    pub execution_payload: ExecutionPayload,
+    /// The fees the fee recipient receives, in wei
+    #[serde(rename = "blockValue")]
+    pub block_value: U256,
+}
+
+/// Validation status of a payload, returned by the Engine API
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct PayloadStatus {
+    pub status: PayloadValidity,
+    #[serde(rename = "latestValidHash")]
+    pub latest_valid_hash: Option<H256>,
+    #[serde(rename = "validationError")]
+    pub validation_error: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PayloadValidity {
+    Valid,
+    Invalid,
+    Syncing,
+    Accepted,
+    InvalidBlockHash,
+}
+
+/// The fork choice of the consensus layer
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ForkchoiceState {
+    #[serde(rename = "headBlockHash")]
+    pub head_block_hash: H256,
+    #[serde(rename = "safeBlockHash")]
+    pub safe_block_hash: H256,
+    #[serde(rename = "finalizedBlockHash")]
+    pub finalized_block_hash: H256,
+}
+
+/// Attributes to start building a new payload on top of the fork choice head
+///
+/// `withdrawals` is only used from `V2` on.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PayloadAttributes {
+    pub timestamp: U64,
+    #[serde(rename = "prevRandao")]
+    pub prev_randao: H256,
+    #[serde(rename = "suggestedFeeRecipient")]
+    pub suggested_fee_recipient: H160,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub withdrawals: Option<Vec<Withdrawal>>,
+}
+
+/// Result of `engine_forkchoiceUpdated`
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct ForkchoiceUpdated {
+    #[serde(rename = "payloadStatus")]
+    pub payload_status: PayloadStatus,
+    /// Set if payload building has been started, used for `engine_getPayload`
+    #[serde(rename = "payloadId")]
+    pub payload_id: Option<H64>,
+}
+
+/// Wrapper for node synchronization info
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum SyncInfo {
+    Syncing(SyncStatus),
+    NotSyncing(bool),
+}
+
+/// Wrapper for node synchronization info in a subscription
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct SyncInfoSubscription {
+    pub syncing: bool,
+    pub status: Option<SyncStatus>,
+}
+
+/// Status object containing information about node synchronization
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct SyncStatus {
+    #[serde(rename = "startingBlock")]
+    pub starting_block: u64,
+    #[serde(rename = "currentBlock")]
+    pub current_block: u64,
+    #[serde(rename = "highestBlock")]
+    pub highest_block: u64,
+    #[serde(rename = "pulledStates")]
+    pub pulled_states: Option<u64>,
+    #[serde(rename = "knownStates")]
+    pub known_states: Option<u64>,
+}
+
+/// A wrapper for a signed transaction
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct SignedTransaction {
+    pub raw: Bytes,
+    pub tx: Transaction,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_types_bytes() {
+        let bytes_0 = Bytes::from_slice(&[]);
+        let bytes_1 = Bytes::from_slice(&[0, 0]);
+        let bytes_2 = Bytes::from_slice(&[17, 234]);
+        let bytes_3 = Bytes::from_str("0x").unwrap();
+        let bytes_4 = Bytes::from_str("0x00").unwrap();
+        let bytes_5 = Bytes::from_str("00421100").unwrap();
+
+        let expected_0 = "\"0x\"";
+        let expected_1 = "\"0x0000\"";
+        let expected_2 = "\"0x11ea\"";
+        let expected_3 = Bytes(vec![]);
+        let expected_4 = Bytes(vec![0]);
+        let expected_5 = Bytes(vec![0, 66, 17, 0]);
+
+        assert_eq!(serde_json::to_string(&bytes_0).unwrap(), expected_0);
+        assert_eq!(serde_json::to_string(&bytes_1).unwrap(), expected_1);
+        assert_eq!(serde_json::to_string(&bytes_2).unwrap(), expected_2);
+        assert_eq!(bytes_3, expected_3);
+        assert_eq!(bytes_4, expected_4);
+        assert_eq!(bytes_5, expected_5);
+    }
+
+    #[test]
+    fn test_types_block_parameter() {
+        let block_param_default = BlockParameter::default();
+        let block_param_custom = BlockParameter::Custom(U64::from(11827902));
+
+        assert_eq!(
             serde_json::to_string(&block_param_default).unwrap(),
             "\"latest\""
         );
@@ -445,6 +1799,29 @@ mod tests {
             serde_json::to_string(&block_param_custom).unwrap(),
             "\"0xb47abe\""
         );
+        assert_eq!(
+            serde_json::to_string(&BlockParameter::Safe).unwrap(),
+            "\"safe\""
+        );
+        assert_eq!(
+            serde_json::to_string(&BlockParameter::Finalized).unwrap(),
+            "\"finalized\""
+        );
+        assert_eq!(
+            serde_json::to_string(&BlockId::from(BlockParameter::Safe)).unwrap(),
+            "\"safe\""
+        );
+        assert_eq!(
+            serde_json::to_value(&BlockId::Hash {
+                hash: H256::from_low_u64_be(1),
+                require_canonical: true,
+            })
+            .unwrap(),
+            serde_json::json!({
+                "blockHash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+                "requireCanonical": true
+            })
+        );
     }
 
     #[test]
@@ -463,4 +1840,766 @@ mod tests {
             "\"e4745d1287b67412ce806746e83d49efe5cec53f5a27aa666fb9e8092a8dbd43\""
         );
     }
+
+    #[test]
+    fn test_types_trace_options() {
+        let default_options = TraceOptions::default();
+        let call_tracer_options = TraceOptions {
+            disable_storage: Some(true),
+            tracer: Some(Tracer::CallTracer),
+            timeout: Some(String::from("10s")),
+            ..Default::default()
+        };
+
+        assert_eq!(serde_json::to_string(&default_options).unwrap(), "{}");
+        assert_eq!(
+            serde_json::to_string(&call_tracer_options).unwrap(),
+            "{\"disableStorage\":true,\"tracer\":\"callTracer\",\"timeout\":\"10s\"}"
+        );
+    }
+
+    #[test]
+    fn test_types_trace_result() {
+        let struct_logs = serde_json::json!({
+            "gas": 21000,
+            "failed": false,
+            "returnValue": "",
+            "structLogs": [{
+                "pc": 0,
+                "op": "PUSH1",
+                "gas": 78000,
+                "gasCost": 3,
+                "depth": 1,
+                "stack": ["0x1"],
+                "storage": {
+                    "0000000000000000000000000000000000000000000000000000000000000000":
+                    "0000000000000000000000000000000000000000000000000000000000000001"
+                }
+            }]
+        });
+        let call_frame = serde_json::json!({
+            "type": "CALL",
+            "from": "0x0216d5032f356960cd3749c31ab34eeff21b3395",
+            "to": "0x326b2a9d3dff4f7e8dfb3aeb1e6b3f4d0c5fe7a6",
+            "value": "0x0",
+            "gas": "0x7148",
+            "gasUsed": "0x5208",
+            "input": "0x",
+            "calls": [{
+                "type": "DELEGATECALL",
+                "from": "0x326b2a9d3dff4f7e8dfb3aeb1e6b3f4d0c5fe7a6",
+                "to": "0x0000000000000000000000000000000000000001",
+                "gas": "0x100",
+                "gasUsed": "0x10",
+                "input": "0x01",
+                "output": "0x",
+                "error": "execution reverted"
+            }]
+        });
+        let prestate = serde_json::json!({
+            "0x0216d5032f356960cd3749c31ab34eeff21b3395": {
+                "balance": "0x10",
+                "nonce": 1
+            }
+        });
+
+        match serde_json::from_value::<TraceResult>(struct_logs).unwrap() {
+            TraceResult::StructLogs(trace) => {
+                assert_eq!(trace.struct_logs[0].op, "PUSH1");
+                assert_eq!(trace.struct_logs[0].stack, Some(vec![U256::one()]));
+                assert_eq!(
+                    trace.struct_logs[0].storage.as_ref().unwrap()[&H256::zero()],
+                    H256::from_low_u64_be(1)
+                );
+            }
+            other => panic!("Expected struct logs, got {:?}", other),
+        }
+        match serde_json::from_value::<TraceResult>(call_frame).unwrap() {
+            TraceResult::CallFrame(frame) => {
+                assert_eq!(frame.call_type, CallType::Call);
+                assert_eq!(frame.calls[0].call_type, CallType::DelegateCall);
+                assert_eq!(frame.calls[0].error.as_deref(), Some("execution reverted"));
+                assert!(frame.calls[0].calls.is_empty());
+            }
+            other => panic!("Expected call frame, got {:?}", other),
+        }
+        match serde_json::from_value::<TraceResult>(prestate).unwrap() {
+            TraceResult::Prestate(accounts) => {
+                let account = accounts.values().next().unwrap();
+                assert_eq!(account.balance, Some(U256::from(16)));
+                assert_eq!(account.nonce, Some(1));
+            }
+            other => panic!("Expected prestate, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_types_localized_trace() {
+        let traces: Vec<LocalizedTrace> = serde_json::from_value(serde_json::json!([
+            {
+                "action": {
+                    "callType": "call",
+                    "from": "0x0216d5032f356960cd3749c31ab34eeff21b3395",
+                    "gas": "0x1dcd12f8",
+                    "input": "0x",
+                    "to": "0x326b2a9d3dff4f7e8dfb3aeb1e6b3f4d0c5fe7a6",
+                    "value": "0x2386f26fc10000"
+                },
+                "blockHash": "0x7eb25504e4c202cf3d62fd585d3e238f592c780cca82dacb2ed3cb5b38883add",
+                "blockNumber": 3068185,
+                "result": { "gasUsed": "0x0", "output": "0x" },
+                "subtraces": 0,
+                "traceAddress": [],
+                "transactionHash": "0x07da28d752aba3b9dd7060005e554719c6205c8a3aea358599fc9b245c52f1f6",
+                "transactionPosition": 0,
+                "type": "call"
+            },
+            {
+                "action": {
+                    "from": "0x0216d5032f356960cd3749c31ab34eeff21b3395",
+                    "gas": "0x100",
+                    "init": "0x6000",
+                    "value": "0x0"
+                },
+                "blockHash": "0x7eb25504e4c202cf3d62fd585d3e238f592c780cca82dacb2ed3cb5b38883add",
+                "blockNumber": 3068185,
+                "result": {
+                    "address": "0x326b2a9d3dff4f7e8dfb3aeb1e6b3f4d0c5fe7a6",
+                    "code": "0x",
+                    "gasUsed": "0x10"
+                },
+                "subtraces": 0,
+                "traceAddress": [0],
+                "transactionHash": null,
+                "transactionPosition": null,
+                "type": "create"
+            },
+            {
+                "action": {
+                    "author": "0x0216d5032f356960cd3749c31ab34eeff21b3395",
+                    "rewardType": "block",
+                    "value": "0x1bc16d674ec80000"
+                },
+                "blockHash": "0x7eb25504e4c202cf3d62fd585d3e238f592c780cca82dacb2ed3cb5b38883add",
+                "blockNumber": 3068185,
+                "result": null,
+                "subtraces": 0,
+                "traceAddress": [],
+                "type": "reward"
+            }
+        ]))
+        .unwrap();
+
+        match &traces[0].action {
+            Action::Call(call) => assert_eq!(call.call_type, CallType::Call),
+            other => panic!("Expected call action, got {:?}", other),
+        }
+        assert!(matches!(traces[0].result, Some(ActionResult::Call(_))));
+        assert!(matches!(traces[1].action, Action::Create(_)));
+        assert!(matches!(traces[1].result, Some(ActionResult::Create(_))));
+        match &traces[2].action {
+            Action::Reward(reward) => assert_eq!(reward.reward_type, RewardType::Block),
+            other => panic!("Expected reward action, got {:?}", other),
+        }
+        assert_eq!(traces[2].transaction_hash, None);
+    }
+
+    #[test]
+    fn test_types_trace_action_uses_type() {
+        let trace = |trace_type: &str| {
+            serde_json::from_value::<Trace>(serde_json::json!({
+                "action": {
+                    "callType": "call",
+                    "from": "0x0216d5032f356960cd3749c31ab34eeff21b3395",
+                    "gas": "0x100",
+                    "init": "0x6000",
+                    "input": "0x",
+                    "to": "0x326b2a9d3dff4f7e8dfb3aeb1e6b3f4d0c5fe7a6",
+                    "value": "0x0"
+                },
+                "result": null,
+                "subtraces": 0,
+                "traceAddress": [],
+                "type": trace_type
+            }))
+        };
+        // The action has the fields of both a call and a create
+        assert!(matches!(trace("call").unwrap().action, Action::Call(_)));
+        assert!(matches!(trace("create").unwrap().action, Action::Create(_)));
+        assert!(trace("suicide").is_err());
+    }
+
+    #[test]
+    fn test_types_trace_results_state_diff() {
+        let results: TraceResults = serde_json::from_value(serde_json::json!({
+            "output": "0x",
+            "trace": null,
+            "vmTrace": {
+                "code": "0x6001",
+                "ops": [{
+                    "pc": 0,
+                    "cost": 3,
+                    "ex": { "used": 99997, "push": ["0x1"], "mem": null, "store": null },
+                    "sub": null
+                }]
+            },
+            "stateDiff": {
+                "0x0216d5032f356960cd3749c31ab34eeff21b3395": {
+                    "balance": { "*": { "from": "0x10", "to": "0x8" } },
+                    "nonce": { "+": "0x0" },
+                    "code": "=",
+                    "storage": {}
+                }
+            }
+        }))
+        .unwrap();
+
+        let diff = results.state_diff.unwrap();
+        let account = diff.values().next().unwrap();
+        assert_eq!(
+            account.balance,
+            Delta::Changed(ChangedValue {
+                from: U256::from(16),
+                to: U256::from(8)
+            })
+        );
+        assert_eq!(account.nonce, Delta::Added(U256::zero()));
+        assert_eq!(account.code, Delta::Unchanged);
+        assert_eq!(
+            results.vm_trace.unwrap().ops[0].ex.as_ref().unwrap().push,
+            vec![U256::one()]
+        );
+    }
+
+    #[test]
+    fn test_types_peer_info() {
+        let peer: PeerInfo = serde_json::from_value(serde_json::json!({
+            "enode": "enode://44826a5d6a55f88a18298bca4773fca5749cdc3a5c9f308aa7d810e9b31123f3e7c5fba0b1d70aac5308426f47df2a128a6747040a3815cc7dd7167d03be320d@[::]:30303",
+            "id": "44826a5d6a55f88a18298bca4773fca5749cdc3a5c9f308aa7d810e9b31123f",
+            "name": "Geth/v1.10.1-stable/linux-amd64/go1.16",
+            "caps": ["eth/66", "snap/1"],
+            "network": {
+                "localAddress": "192.168.0.10:30303",
+                "remoteAddress": "10.0.0.1:40404",
+                "inbound": true,
+                "trusted": false,
+                "static": false
+            },
+            "protocols": { "eth": { "version": 66 } }
+        }))
+        .unwrap();
+
+        assert_eq!(peer.enr, None);
+        assert!(peer.network.inbound);
+        assert!(!peer.network.static_node);
+        assert_eq!(peer.protocols["eth"]["version"], 66);
+    }
+
+    fn genesis_block() -> serde_json::Value {
+        serde_json::json!({
+            "number": "0x0",
+            "hash": "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+            "parentHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "nonce": "0x0000000000000042",
+            "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+            "logsBloom": format!("0x{}", "0".repeat(512)),
+            "transactionsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "stateRoot": "0xd7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544",
+            "receiptsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "miner": "0x0000000000000000000000000000000000000000",
+            "difficulty": "0x400000000",
+            "totalDifficulty": "0x400000000",
+            "extraData": "0x11bbe8db4e347b4e8c937c1c8370e4b5ed33adb3db69cbdb7a38e1e50b1b82fa",
+            "size": "0x21c",
+            "gasLimit": "0x1388",
+            "gasUsed": "0x0",
+            "timestamp": "0x0",
+            "transactions": [],
+            "uncles": [],
+            "mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000"
+        })
+    }
+
+    #[test]
+    fn test_types_block_post_merge() {
+        let mut json = genesis_block();
+        let object = json.as_object_mut().unwrap();
+        object.remove("totalDifficulty");
+        object.insert(String::from("difficulty"), serde_json::json!("0x0"));
+        object.insert(String::from("baseFeePerGas"), serde_json::json!("0x7"));
+        object.insert(
+            String::from("withdrawalsRoot"),
+            serde_json::json!("0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"),
+        );
+        object.insert(
+            String::from("withdrawals"),
+            serde_json::json!([{
+                "index": "0xa",
+                "validatorIndex": "0x5",
+                "address": "0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b",
+                "amount": "0x3b9aca00"
+            }]),
+        );
+        let block: Block = serde_json::from_value(json).unwrap();
+
+        assert!(block.is_post_merge());
+        assert_eq!(block.total_difficulty, None);
+        assert_eq!(block.base_fee_per_gas, Some(U256::from(7)));
+        assert_eq!(block.withdrawals.unwrap()[0].validator_index, U64::from(5));
+    }
+
+    #[test]
+    fn test_types_block_hash() {
+        let block: Block = serde_json::from_value(genesis_block()).unwrap();
+        assert_eq!(block.compute_hash().unwrap(), block.hash.unwrap());
+        assert!(block.verify_hash().is_ok());
+        assert!(BlockHeader::from(&block).verify_hash().is_ok());
+    }
+
+    #[test]
+    fn test_types_block_hash_tampered() {
+        let mut block: Block = serde_json::from_value(genesis_block()).unwrap();
+        block.gas_limit = U256::from(5001);
+        assert!(matches!(
+            block.verify_hash(),
+            Err(BlockHashError::Mismatch { .. })
+        ));
+
+        block.mix_hash = None;
+        assert!(matches!(
+            block.verify_hash(),
+            Err(BlockHashError::MissingField("mixHash"))
+        ));
+    }
+
+    #[test]
+    fn test_types_execution_payload() {
+        let payload = serde_json::json!({
+            "parentHash": "0x3b8fb240d288781d4aac94d3fd16809ee413bc99294a085798a589dae51ddd4a",
+            "feeRecipient": "0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b",
+            "stateRoot": "0xca3149fa9e37db08d1cd49c9061db1002ef1cd58db2210f2115c8c989b2bdf45",
+            "receiptsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "logsBloom": format!("0x{}", "0".repeat(512)),
+            "prevRandao": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "blockNumber": "0x1",
+            "gasLimit": "0x1c95111",
+            "gasUsed": "0x0",
+            "timestamp": "0x5",
+            "extraData": "0x",
+            "baseFeePerGas": "0x7",
+            "blockHash": "0x6359b8381a370e2f54072a5784ddd78b6ed024991558c511d4452eb4f6ac898c",
+            "transactions": ["0xf86c"],
+            "withdrawals": [{
+                "index": "0x0",
+                "validatorIndex": "0x1",
+                "address": "0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b",
+                "amount": "0x64"
+            }]
+        });
+        let deserialized: ExecutionPayload = serde_json::from_value(payload.clone()).unwrap();
+
+        assert_eq!(
+            deserialized.withdrawals.as_ref().unwrap()[0].amount,
+            U64::from(100)
+        );
+        assert_eq!(serde_json::to_value(&deserialized).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_types_forkchoice_updated() {
+        let updated: ForkchoiceUpdated = serde_json::from_value(serde_json::json!({
+            "payloadStatus": {
+                "status": "INVALID_BLOCK_HASH",
+                "latestValidHash": null,
+                "validationError": "invalid block hash"
+            },
+            "payloadId": "0xa247243752eb10b4"
+        }))
+        .unwrap();
+
+        assert_eq!(
+            updated.payload_status.status,
+            PayloadValidity::InvalidBlockHash
+        );
+        assert!(updated.payload_id.is_some());
+    }
+
+    fn blob_sidecar() -> BlobSidecar {
+        BlobSidecar {
+            blobs: vec![Bytes(vec![1; BlobSidecar::BLOB_SIZE])],
+            commitments: vec![Bytes(vec![2; BlobSidecar::KZG_SIZE])],
+            proofs: vec![Bytes(vec![3; BlobSidecar::KZG_SIZE])],
+        }
+    }
+
+    #[test]
+    fn test_types_blob_sidecar() {
+        let sidecar = blob_sidecar();
+        let hashes = sidecar.versioned_hashes();
+
+        assert!(sidecar.validate().is_ok());
+        assert_eq!(hashes.len(), 1);
+        assert_eq!(hashes[0][0], 0x01);
+        assert_eq!(
+            hashes[0][1..],
+            hmac_sha256::Hash::hash(&sidecar.commitments[0].0)[1..]
+        );
+
+        let mut missing_proof = sidecar.clone();
+        missing_proof.proofs.clear();
+        assert!(matches!(
+            missing_proof.validate(),
+            Err(BlobError::CountMismatch)
+        ));
+
+        let mut short_blob = sidecar;
+        short_blob.blobs[0].0.pop();
+        assert!(matches!(
+            short_blob.validate(),
+            Err(BlobError::InvalidSize { .. })
+        ));
+        assert!(matches!(
+            BlobSidecar::default().validate(),
+            Err(BlobError::Empty)
+        ));
+    }
+
+    #[test]
+    fn test_types_blob_sidecar_wrap() {
+        let sidecar = blob_sidecar();
+        let mut payload_body = RlpStream::new_list(2);
+        payload_body.append(&1u8).append(&2u8);
+        let mut signed = vec![0x03];
+        signed.extend_from_slice(&payload_body.out());
+
+        let wrapped = sidecar.wrap_signed_transaction(&Bytes(signed)).unwrap();
+        assert_eq!(wrapped.0[0], 0x03);
+        let rlp = rlp::Rlp::new(&wrapped.0[1..]);
+        assert_eq!(rlp.item_count().unwrap(), 4);
+        assert_eq!(rlp.at(0).unwrap().as_list::<u8>().unwrap(), vec![1, 2]);
+        assert_eq!(
+            rlp.at(1).unwrap().at(0).unwrap().data().unwrap(),
+            &sidecar.blobs[0].0[..]
+        );
+        assert!(matches!(
+            sidecar.wrap_signed_transaction(&Bytes(vec![0x02, 0xc0])),
+            Err(BlobError::NoBlobTransaction)
+        ));
+    }
+
+    #[test]
+    fn test_types_transaction_request_sidecar() {
+        let request = TransactionRequest {
+            max_fee_per_blob_gas: Some(U256::one()),
+            sidecar: Some(BlobSidecar {
+                blobs: vec![Bytes(vec![1])],
+                commitments: vec![Bytes(vec![2])],
+                proofs: vec![Bytes(vec![3])],
+            }),
+            ..Default::default()
+        };
+        let serialized = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(serialized["maxFeePerBlobGas"], "0x1");
+        assert_eq!(serialized["blobs"], serde_json::json!(["0x01"]));
+        assert_eq!(serialized["proofs"], serde_json::json!(["0x03"]));
+        assert!(serialized.get("sidecar").is_none());
+        assert!(serde_json::to_value(&TransactionRequest::default())
+            .unwrap()
+            .get("blobs")
+            .is_none());
+    }
+
+    #[test]
+    fn test_types_signature() {
+        let bytes = Bytes::from_str(concat!(
+            "0x68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90",
+            "7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea520641c",
+        ))
+        .unwrap();
+        let signature = Signature::try_from(&bytes).unwrap();
+        assert_eq!(signature.v, 28);
+        assert_eq!(signature.recovery_id(), Ok(1));
+        assert_eq!(signature.to_bytes().unwrap(), bytes);
+
+        // Example of EIP-2098
+        let compact = Bytes::from_str(concat!(
+            "0x68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90",
+            "fe865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064",
+        ))
+        .unwrap();
+        assert_eq!(signature.to_compact().unwrap(), compact);
+        assert_eq!(Signature::try_from(&compact).unwrap(), signature);
+
+        let eip155 = Signature { v: 37, ..signature };
+        assert_eq!(eip155.recovery_id(), Ok(0));
+        let invalid = Signature { v: 29, ..signature };
+        assert_eq!(invalid.to_bytes(), Err(SignatureError::RecoveryId(29)));
+        assert_eq!(
+            Signature::from_bytes(&[0u8; 20]),
+            Err(SignatureError::Length(20))
+        );
+    }
+
+    #[test]
+    fn test_types_address_checksum() {
+        let addresses = [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ];
+        for checksummed in addresses.iter() {
+            let address = Address::from_str(checksummed).unwrap();
+            assert_eq!(address.to_string(), *checksummed);
+            assert_eq!(
+                Address::from_str(&checksummed.to_lowercase()).unwrap(),
+                address
+            );
+            assert_eq!(
+                Address::from_str(&checksummed[2..].to_uppercase()).unwrap(),
+                address
+            );
+        }
+
+        assert!(matches!(
+            Address::from_str("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"),
+            Err(AddressError::Checksum(_))
+        ));
+        assert!(matches!(
+            Address::from_str("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA"),
+            Err(AddressError::Hex(_))
+        ));
+        assert!(
+            serde_json::from_str::<Address>(r#""0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD""#)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_types_address_checksum_chain_id() {
+        let rsk_mainnet = [
+            "0x5aaEB6053f3e94c9b9a09f33669435E7ef1bEAeD",
+            "0xFb6916095cA1Df60bb79ce92cE3EA74c37c5d359",
+            "0xDBF03B407c01E7CD3cBea99509D93F8Dddc8C6FB",
+            "0xD1220A0Cf47c7B9BE7a2e6ba89F429762E7B9adB",
+        ];
+        for checksummed in rsk_mainnet.iter() {
+            let address = Address::from_str_with_chain_id(checksummed, Some(30)).unwrap();
+            assert_eq!(address.to_checksum(Some(30)), *checksummed);
+            assert!(Address::from_str(checksummed).is_err());
+        }
+    }
+
+    #[test]
+    fn test_types_access_list_result() {
+        let result: AccessListResult = serde_json::from_str(
+            r#"{
+                "accessList": [{
+                    "address": "0xa02457e5dfd32bda5fc7e1f1b008aa5979568150",
+                    "storageKeys": ["0x0000000000000000000000000000000000000000000000000000000000000081"]
+                }],
+                "gasUsed": "0x125f8"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(result.gas_used, U256::from(75256));
+        assert_eq!(result.error, None);
+
+        let transaction = TransactionRequest::default().with_access_list(result.clone());
+        assert_eq!(transaction.access_list, Some(result.access_list));
+        assert_eq!(
+            serde_json::to_value(&transaction).unwrap()["accessList"],
+            serde_json::json!([{
+                "address": "0xa02457e5dfd32bda5fc7e1f1b008aa5979568150",
+                "storageKeys": ["0x0000000000000000000000000000000000000000000000000000000000000081"]
+            }])
+        );
+    }
+
+    #[test]
+    fn test_types_state_override() {
+        let address = H160::from_low_u64_be(1);
+        let mut state_override = StateOverride::new();
+        state_override.insert(
+            address,
+            AccountOverride {
+                balance: Some(U256::exp10(18)),
+                state_diff: Some(
+                    vec![(H256::zero(), H256::from_low_u64_be(2))]
+                        .into_iter()
+                        .collect(),
+                ),
+                ..Default::default()
+            },
+        );
+        let block_overrides = BlockOverrides {
+            number: Some(U64::from(16)),
+            base_fee_per_gas: Some(U256::zero()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serde_json::to_value(&state_override).unwrap(),
+            serde_json::json!({
+                "0x0000000000000000000000000000000000000001": {
+                    "balance": "0xde0b6b3a7640000",
+                    "stateDiff": {
+                        "0x0000000000000000000000000000000000000000000000000000000000000000":
+                        "0x0000000000000000000000000000000000000000000000000000000000000002"
+                    }
+                }
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(&block_overrides).unwrap(),
+            serde_json::json!({"number": "0x10", "baseFeePerGas": "0x0"})
+        );
+    }
+
+    const TXPOOL_SENDER: &str = "0x0216d5032f356960cd3749c31ab34eeff21b3395";
+
+    fn txpool_transaction(nonce: &str) -> serde_json::Value {
+        serde_json::json!({
+            "blockHash": null,
+            "blockNumber": null,
+            "from": TXPOOL_SENDER,
+            "gas": "0x5208",
+            "gasPrice": "0x2",
+            "hash": "0x5b2b2ab16cd3dfcfa4a1a3e4edbe91f5b3b5ded42d7e1d2c8fbf8ffc0e8f3b7b",
+            "input": "0x",
+            "nonce": nonce,
+            "to": "0x0000000000000000000000000000000000000000",
+            "transactionIndex": null,
+            "value": "0x3",
+            "v": "0x1b",
+            "r": "0x1",
+            "s": "0x1"
+        })
+    }
+
+    #[test]
+    fn test_types_txpool_content() {
+        let content: TxPoolContent = serde_json::from_value(serde_json::json!({
+            "pending": {
+                TXPOOL_SENDER: { "10": txpool_transaction("0xa"), "2": txpool_transaction("0x2") }
+            },
+            "queued": { TXPOOL_SENDER: { "5": txpool_transaction("0x5") } }
+        }))
+        .unwrap();
+        let sender = H160::from_str(TXPOOL_SENDER).unwrap();
+
+        let pending_nonces = content
+            .pending_by_nonce(&sender)
+            .iter()
+            .map(|tx| tx.nonce.as_u64())
+            .collect::<Vec<u64>>();
+        assert_eq!(pending_nonces, vec![2, 10]);
+        assert_eq!(content.queued_by_nonce(&sender).len(), 1);
+        assert!(content.pending_by_nonce(&H160::zero()).is_empty());
+        assert_eq!(
+            content.nonce_gaps(&sender),
+            vec![U256::from(3)..=U256::from(4), U256::from(6)..=U256::from(9)]
+        );
+        assert_eq!(
+            content.totals(&sender),
+            TxPoolTotals {
+                count: 3,
+                value: U256::from(9),
+                gas: U256::from(63000),
+                fees: U256::from(126000),
+            }
+        );
+    }
+
+    #[test]
+    fn test_types_txpool_nonce_gaps_wide() {
+        let mut content = TxPoolContent {
+            pending: HashMap::new(),
+            queued: HashMap::new(),
+        };
+        let transaction: Transaction = serde_json::from_value(txpool_transaction("0x0")).unwrap();
+        let sender = H160::from_str(TXPOOL_SENDER).unwrap();
+        let wide = U256::from(u64::MAX) + 1;
+        content.pending.insert(
+            sender,
+            vec![(U256::zero(), transaction.clone()), (wide, transaction)]
+                .into_iter()
+                .collect(),
+        );
+        assert_eq!(content.nonce_gaps(&sender), vec![U256::one()..=wide - 1]);
+    }
+
+    #[test]
+    fn test_types_txpool_content_invalid_nonce() {
+        let content = serde_json::from_value::<TxPoolContent>(serde_json::json!({
+            "pending": { "0x0216d5032f356960cd3749c31ab34eeff21b3395": { "0x1": {} } },
+            "queued": {}
+        }));
+        assert!(content.is_err());
+    }
+
+    #[test]
+    fn test_types_txpool_content_from() {
+        let content: TxPoolContentFrom = serde_json::from_value(serde_json::json!({
+            "pending": {},
+            "queued": {}
+        }))
+        .unwrap();
+        assert!(content.pending.is_empty());
+        assert!(content.queued.is_empty());
+
+        let content: TxPoolContentFrom = serde_json::from_value(serde_json::json!({
+            "pending": { "10": txpool_transaction("0xa"), "9": txpool_transaction("0x9") },
+            "queued": { "12": txpool_transaction("0xc") }
+        }))
+        .unwrap();
+        assert_eq!(
+            content.pending.keys().copied().collect::<Vec<U256>>(),
+            vec![U256::from(9), U256::from(10)]
+        );
+        assert_eq!(content.pending[&U256::from(10)].nonce, U256::from(10));
+        assert_eq!(content.queued[&U256::from(12)].nonce, U256::from(12));
+        assert!(
+            serde_json::from_value::<TxPoolContentFrom>(serde_json::json!({
+                "pending": { "0xa": txpool_transaction("0xa") },
+                "queued": {}
+            }))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_types_txpool_summary() {
+        let transfer = TxPoolSummary::from_str(
+            "0x326B2A9d3dFf4F7E8DFb3AEb1e6b3F4d0C5FE7a6: 1000 wei + 21000 gas × 20000000000 wei",
+        )
+        .unwrap();
+        let creation =
+            TxPoolSummary::from_str("contract creation: 0 wei + 90000 gas × 1 wei").unwrap();
+
+        assert_eq!(
+            transfer,
+            TxPoolSummary {
+                to: Some(H160::from_str("0x326b2a9d3dff4f7e8dfb3aeb1e6b3f4d0c5fe7a6").unwrap()),
+                value: U256::from(1000),
+                gas: U256::from(21000),
+                gas_price: U256::from(20000000000u64),
+            }
+        );
+        assert_eq!(creation.to, None);
+        assert_eq!(creation.gas, U256::from(90000));
+        assert!(matches!(
+            TxPoolSummary::from_str("contract creation: 0 wei"),
+            Err(TxPoolSummaryError::Malformed(_))
+        ));
+        assert!(matches!(
+            TxPoolSummary::from_str("0xzz: 0 wei + 1 gas × 1 wei"),
+            Err(TxPoolSummaryError::Address(_))
+        ));
+        assert!(matches!(
+            TxPoolSummary::from_str("contract creation: 0x1 wei + 1 gas × 1 wei"),
+            Err(TxPoolSummaryError::Number(_))
+        ));
+    }
 }
@@ -0,0 +1,253 @@
+//! Conversion between decimal amounts and integer base units like wei
+//!
+//! Amounts are handled as decimal strings and converted exactly, without floating point
+//! arithmetic. Units are given either by name, like `"gwei"` or `"ether"`, or by the number of
+//! decimals, like the `decimals()` of an ERC-20 token.
+//!
+//! ```
+//! use ethane::units::{format_units, parse_units};
+//!
+//! let wei = parse_units("1.5", "ether").unwrap();
+//! assert_eq!(wei, ethane::types::U256::from(1_500_000_000_000_000_000u64));
+//! assert_eq!(format_units(wei, "gwei").unwrap(), "1500000000");
+//! assert_eq!(format_units(wei, 18).unwrap(), "1.5");
+//! ```
+
+use crate::types::U256;
+use std::convert::{Infallible, TryFrom, TryInto};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// The number of decimals of a unit
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Units {
+    Wei,
+    Kwei,
+    Mwei,
+    Gwei,
+    Szabo,
+    Finney,
+    Ether,
+    /// Arbitrary decimals, at most 77 so that the unit itself fits into [U256]
+    Other(u8),
+}
+
+impl Units {
+    const MAX_DECIMALS: u32 = 77;
+
+    pub fn decimals(&self) -> u8 {
+        match self {
+            Units::Wei => 0,
+            Units::Kwei => 3,
+            Units::Mwei => 6,
+            Units::Gwei => 9,
+            Units::Szabo => 12,
+            Units::Finney => 15,
+            Units::Ether => 18,
+            Units::Other(decimals) => *decimals,
+        }
+    }
+}
+
+impl FromStr for Units {
+    type Err = UnitsError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "wei" => Ok(Units::Wei),
+            "kwei" => Ok(Units::Kwei),
+            "mwei" => Ok(Units::Mwei),
+            "gwei" => Ok(Units::Gwei),
+            "szabo" => Ok(Units::Szabo),
+            "finney" => Ok(Units::Finney),
+            "ether" => Ok(Units::Ether),
+            _ => Err(UnitsError::UnknownUnit(value.to_string())),
+        }
+    }
+}
+
+impl TryFrom<&str> for Units {
+    type Error = UnitsError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Units::from_str(value)
+    }
+}
+
+impl TryFrom<u32> for Units {
+    type Error = UnitsError;
+
+    fn try_from(decimals: u32) -> Result<Self, Self::Error> {
+        if decimals > Self::MAX_DECIMALS {
+            return Err(UnitsError::Decimals(decimals as i64));
+        }
+        Ok(Units::Other(decimals as u8))
+    }
+}
+
+impl TryFrom<u8> for Units {
+    type Error = UnitsError;
+
+    fn try_from(decimals: u8) -> Result<Self, Self::Error> {
+        Units::try_from(decimals as u32)
+    }
+}
+
+impl TryFrom<i32> for Units {
+    type Error = UnitsError;
+
+    fn try_from(decimals: i32) -> Result<Self, Self::Error> {
+        let decimals =
+            u32::try_from(decimals).map_err(|_| UnitsError::Decimals(decimals as i64))?;
+        Units::try_from(decimals)
+    }
+}
+
+/// Converts a decimal amount like `"1.5"` into an integer amount of base units
+///
+/// Fails if the amount has more fractional digits than the unit has decimals, unless they are
+/// zero, or if the result does not fit into [U256].
+pub fn parse_units<U>(value: &str, units: U) -> Result<U256, UnitsError>
+where
+    U: TryInto<Units>,
+    UnitsError: From<U::Error>,
+{
+    let decimals = units.try_into()?.decimals() as usize;
+    let invalid = || UnitsError::InvalidNumber(value.to_string());
+
+    let (integer, fraction) = match value.split_once('.') {
+        Some((integer, fraction)) => (integer, fraction),
+        None => (value, ""),
+    };
+    let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if integer.len() + fraction.len() == 0 || !is_digits(integer) || !is_digits(fraction) {
+        return Err(invalid());
+    }
+
+    let (fraction, dropped) = fraction.split_at(fraction.len().min(decimals));
+    if dropped.chars().any(|c| c != '0') {
+        return Err(UnitsError::PrecisionLoss(value.to_string()));
+    }
+
+    let digits = format!("{}{:0<width$}", integer, fraction, width = decimals);
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Ok(U256::zero());
+    }
+    U256::from_dec_str(digits).map_err(|_| UnitsError::Overflow(value.to_string()))
+}
+
+/// Formats an integer amount of base units as decimal amount, without trailing zeros
+pub fn format_units<U>(value: U256, units: U) -> Result<String, UnitsError>
+where
+    U: TryInto<Units>,
+    UnitsError: From<U::Error>,
+{
+    let decimals = units.try_into()?.decimals() as usize;
+    let digits = format!("{:0>width$}", value.to_string(), width = decimals + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        Ok(integer.to_string())
+    } else {
+        Ok(format!("{}.{}", integer, fraction))
+    }
+}
+
+/// An error type collecting what can go wrong when converting units
+#[derive(Debug, Error, PartialEq)]
+pub enum UnitsError {
+    #[error("Units Error: Unknown unit {0}")]
+    UnknownUnit(String),
+    #[error("Units Error: Unsupported number of decimals {0}")]
+    Decimals(i64),
+    #[error("Units Error: Invalid decimal number {0:?}")]
+    InvalidNumber(String),
+    #[error("Units Error: {0} has more decimals than the unit")]
+    PrecisionLoss(String),
+    #[error("Units Error: {0} does not fit into 256 bits")]
+    Overflow(String),
+}
+
+impl From<Infallible> for UnitsError {
+    fn from(err: Infallible) -> Self {
+        match err {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_units_parse() {
+        assert_eq!(parse_units("100", "ether").unwrap(), U256::exp10(20));
+        assert_eq!(
+            parse_units("1.5", "gwei").unwrap(),
+            U256::from(1_500_000_000)
+        );
+        assert_eq!(parse_units("0.000001", 6).unwrap(), U256::one());
+        assert_eq!(
+            parse_units(".5", Units::Ether).unwrap(),
+            U256::exp10(17) * 5
+        );
+        assert_eq!(
+            parse_units("2.500", "wei"),
+            Err(UnitsError::PrecisionLoss(String::from("2.500")))
+        );
+        assert_eq!(parse_units("2.000", "wei").unwrap(), U256::from(2));
+        assert_eq!(parse_units("0", "ether").unwrap(), U256::zero());
+        assert_eq!(
+            parse_units("0.0000001", 6),
+            Err(UnitsError::PrecisionLoss(String::from("0.0000001")))
+        );
+        assert_eq!(
+            parse_units("1e18", "wei"),
+            Err(UnitsError::InvalidNumber(String::from("1e18")))
+        );
+        assert_eq!(
+            parse_units("-1", "wei"),
+            Err(UnitsError::InvalidNumber(String::from("-1")))
+        );
+        assert_eq!(
+            parse_units(".", "wei"),
+            Err(UnitsError::InvalidNumber(String::from(".")))
+        );
+        assert!(matches!(
+            parse_units(
+                "1000000000000000000000000000000000000000000000000000000000000",
+                "ether"
+            ),
+            Err(UnitsError::Overflow(_))
+        ));
+        assert_eq!(
+            parse_units("1", "bitcoin"),
+            Err(UnitsError::UnknownUnit(String::from("bitcoin")))
+        );
+        assert_eq!(parse_units("1", 78), Err(UnitsError::Decimals(78)));
+    }
+
+    #[test]
+    fn test_units_format() {
+        assert_eq!(format_units(U256::exp10(20), "ether").unwrap(), "100");
+        assert_eq!(
+            format_units(U256::from(1_500_000_000), Units::Gwei).unwrap(),
+            "1.5"
+        );
+        assert_eq!(
+            format_units(U256::one(), 18).unwrap(),
+            "0.000000000000000001"
+        );
+        assert_eq!(format_units(U256::zero(), 6u8).unwrap(), "0");
+        assert_eq!(format_units(U256::from(42), 0).unwrap(), "42");
+        assert_eq!(
+            format_units(U256::MAX, 77).unwrap(),
+            "1.15792089237316195423570985008687907853269984665640564039457584007913129639935"
+        );
+        let value = U256::from_dec_str("123456789012345678901").unwrap();
+        assert_eq!(
+            parse_units(&format_units(value, 18).unwrap(), 18).unwrap(),
+            value
+        );
+    }
+}
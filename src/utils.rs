@@ -0,0 +1,118 @@
+//! Keccak-256 hashing and derived helpers
+//!
+//! These compute locally what would otherwise need a round trip to
+//! [web3_sha3](crate::rpc::web3_sha3), like function selectors, event topics and the storage
+//! slots of mapping entries and array elements, which can be queried with
+//! [eth_get_storage_at](crate::rpc::eth_get_storage_at).
+
+use crate::types::{Bytes, H256, U256};
+use ethereum_types::BigEndianHash;
+use tiny_keccak::{Hasher, Keccak};
+
+/// Computes the keccak-256 hash of the input
+pub fn keccak256(input: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    hasher.update(input);
+    let mut out = [0u8; 32];
+    hasher.finalize(&mut out);
+    out
+}
+
+/// Computes the 4 byte function selector of a signature like `transfer(address,uint256)`
+pub fn selector(signature: &str) -> [u8; 4] {
+    let mut selector = [0u8; 4];
+    selector.copy_from_slice(&keccak256(signature.as_bytes())[..4]);
+    selector
+}
+
+/// Computes the first topic of an event with a signature like `Transfer(address,address,uint256)`
+pub fn event_topic(signature: &str) -> H256 {
+    H256::from(keccak256(signature.as_bytes()))
+}
+
+/// Computes the storage slot of `mapping[key]` for a mapping declared at `slot`
+///
+/// This is for keys of value types, which are left padded to 32 bytes, e.g. `H256::from(address)`
+/// or `H256::from_uint(&value)`. Use [mapping_slot_bytes] for `string` and `bytes` keys. Slots
+/// of nested mappings are computed by passing the slot of the outer mapping entry.
+pub fn mapping_slot(key: H256, slot: U256) -> U256 {
+    mapping_slot_bytes(key.as_bytes(), slot)
+}
+
+/// Computes the storage slot of `mapping[key]` for a mapping with `string` or `bytes` keys
+pub fn mapping_slot_bytes(key: &[u8], slot: U256) -> U256 {
+    let mut input = key.to_vec();
+    input.extend_from_slice(H256::from_uint(&slot).as_bytes());
+    U256::from(keccak256(&input))
+}
+
+/// Computes the storage slot of `array[index]` for a dynamic array declared at `slot`
+///
+/// The length of the array is stored at `slot` itself, while the elements start at
+/// `keccak256(slot)`. Elements which take up several slots, like structs, are laid out one after
+/// another with `slots_per_element` slots each. Elements smaller than 16 bytes share slots and are
+/// not covered by this.
+pub fn array_slot(slot: U256, index: U256, slots_per_element: U256) -> U256 {
+    let start = U256::from(keccak256(H256::from_uint(&slot).as_bytes()));
+    // Storage positions wrap around at 2^256 like in the EVM
+    start
+        .overflowing_add(index.overflowing_mul(slots_per_element).0)
+        .0
+}
+
+impl Bytes {
+    /// Computes the keccak-256 hash of the bytes
+    pub fn keccak256(&self) -> H256 {
+        H256::from(keccak256(&self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::H160;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_utils_selector_and_topic() {
+        assert_eq!(
+            selector("transfer(address,uint256)"),
+            [0xa9, 0x05, 0x9c, 0xbb]
+        );
+        assert_eq!(
+            event_topic("Transfer(address,address,uint256)"),
+            H256::from_str("0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef")
+                .unwrap()
+        );
+        assert_eq!(
+            Bytes::from_slice(b"").keccak256(),
+            H256::from_str("0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_utils_storage_slots() {
+        // keccak256(bytes32(0x..01) ‖ bytes32(0)), i.e. balances[address(1)] at slot 0
+        let key = H256::from(H160::from_low_u64_be(1));
+        assert_eq!(
+            mapping_slot(key, U256::zero()),
+            U256::from_str("ada5013122d395ba3c54772283fb069b10426056ef8ca54750cb9bb552a59e7d")
+                .unwrap()
+        );
+        assert_eq!(
+            mapping_slot_bytes(key.as_bytes(), U256::zero()),
+            mapping_slot(key, U256::zero())
+        );
+
+        // Elements of an array at slot 0 start at keccak256(bytes32(0))
+        let start =
+            U256::from_str("290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563")
+                .unwrap();
+        assert_eq!(array_slot(U256::zero(), U256::zero(), U256::one()), start);
+        assert_eq!(
+            array_slot(U256::zero(), U256::from(3), U256::from(2)),
+            start + 6
+        );
+    }
+}
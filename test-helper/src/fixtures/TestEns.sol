@@ -0,0 +1,30 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8;
+
+// Minimal ENS registry and resolver without any ownership of names
+
+contract TestEnsRegistry {
+    mapping(bytes32 => address) public resolver;
+
+    function setResolver(bytes32 node, address _resolver) public {
+        resolver[node] = _resolver;
+    }
+}
+
+contract TestEnsResolver {
+    mapping(bytes32 => address) public addr;
+    mapping(bytes32 => string) public name;
+    mapping(bytes32 => mapping(string => string)) public text;
+
+    function setAddr(bytes32 node, address _addr) public {
+        addr[node] = _addr;
+    }
+
+    function setName(bytes32 node, string calldata _name) public {
+        name[node] = _name;
+    }
+
+    function setText(bytes32 node, string calldata key, string calldata value) public {
+        text[node][key] = value;
+    }
+}
@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8;
+
+// Covers the storage layouts which can be read with the storage module
+
+contract TestStorage {
+    struct Account {
+        uint128 amount;
+        uint64 since;
+        bool frozen;
+        address owner;
+    }
+
+    uint8 small = 7;
+    bool flag = true;
+    address admin;
+    mapping(address => Account) balances;
+    string name = "A name which does not fit into a single storage slot";
+
+    constructor() {
+        admin = msg.sender;
+        balances[msg.sender] = Account(100, 12, true, msg.sender);
+    }
+}
@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8;
+
+// Minimal token contracts, which mint to the deployer and skip the receiver checks of the
+// standards
+
+contract TestErc20 {
+    string public symbol = "TST";
+    uint8 public decimals = 18;
+    mapping(address => uint) public balanceOf;
+    mapping(address => mapping(address => uint)) public allowance;
+
+    event Transfer(address indexed from, address indexed to, uint value);
+    event Approval(address indexed owner, address indexed spender, uint value);
+
+    constructor() {
+        balanceOf[msg.sender] = 1000;
+        emit Transfer(address(0), msg.sender, 1000);
+    }
+
+    function transfer(address to, uint value) public returns (bool) {
+        balanceOf[msg.sender] -= value;
+        balanceOf[to] += value;
+        emit Transfer(msg.sender, to, value);
+        return true;
+    }
+
+    function approve(address spender, uint value) public returns (bool) {
+        allowance[msg.sender][spender] = value;
+        emit Approval(msg.sender, spender, value);
+        return true;
+    }
+}
+
+contract TestErc721 {
+    string public symbol = "NFT";
+    mapping(uint => address) public ownerOf;
+    mapping(address => uint) public balanceOf;
+
+    event Transfer(address indexed from, address indexed to, uint indexed tokenId);
+
+    constructor() {
+        ownerOf[1] = msg.sender;
+        balanceOf[msg.sender] = 1;
+        emit Transfer(address(0), msg.sender, 1);
+    }
+
+    function safeTransferFrom(address from, address to, uint tokenId) public {
+        require(ownerOf[tokenId] == from && from == msg.sender);
+        ownerOf[tokenId] = to;
+        balanceOf[from] -= 1;
+        balanceOf[to] += 1;
+        emit Transfer(from, to, tokenId);
+    }
+}
+
+contract TestErc1155 {
+    mapping(uint => mapping(address => uint)) private balances;
+
+    event TransferSingle(
+        address indexed operator,
+        address indexed from,
+        address indexed to,
+        uint id,
+        uint value
+    );
+
+    constructor() {
+        balances[1][msg.sender] = 10;
+        emit TransferSingle(msg.sender, address(0), msg.sender, 1, 10);
+    }
+
+    function balanceOf(address owner, uint id) public view returns (uint) {
+        return balances[id][owner];
+    }
+
+    function balanceOfBatch(address[] calldata owners, uint[] calldata ids)
+        public
+        view
+        returns (uint[] memory batch)
+    {
+        batch = new uint[](owners.length);
+        for (uint i = 0; i < owners.length; i++) {
+            batch[i] = balances[ids[i]][owners[i]];
+        }
+    }
+
+    function safeTransferFrom(address from, address to, uint id, uint value, bytes calldata) public {
+        require(from == msg.sender);
+        balances[id][from] -= value;
+        balances[id][to] += value;
+        emit TransferSingle(msg.sender, from, to, id, value);
+    }
+}
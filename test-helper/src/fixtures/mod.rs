@@ -1,5 +1,16 @@
 pub const TEST_CONTRACT_PATH: &str = "./test-helper/src/fixtures/TestContract.sol";
 pub const TEST_CONTRACT_NAME: &str = "TestContract";
+pub const TEST_TOKENS_PATH: &str = "./test-helper/src/fixtures/TestTokens.sol";
+pub const TEST_ERC20_NAME: &str = "TestErc20";
+pub const TEST_ERC721_NAME: &str = "TestErc721";
+pub const TEST_ERC1155_NAME: &str = "TestErc1155";
+pub const TEST_ENS_PATH: &str = "./test-helper/src/fixtures/TestEns.sol";
+pub const TEST_ENS_REGISTRY_NAME: &str = "TestEnsRegistry";
+pub const TEST_ENS_RESOLVER_NAME: &str = "TestEnsResolver";
+pub const TEST_MULTICALL_PATH: &str = "./test-helper/src/fixtures/TestMulticall.sol";
+pub const TEST_MULTICALL_NAME: &str = "TestMulticall";
+pub const TEST_STORAGE_PATH: &str = "./test-helper/src/fixtures/TestStorage.sol";
+pub const TEST_STORAGE_NAME: &str = "TestStorage";
 pub const ACCOUNTS_PASSWORD: &str = "12345678";
 pub const FIX_SECRET: &str = "fdc861959d1768d936bf17eec56260d4de3a7473e58c349e31beba539e5fc88d";
 pub const FIX_ADDRESS: &str = "0xDc677f7C5060B0b441d30F361D0c8529Ac04E099";
@@ -1,5 +1,7 @@
 use ethane::rpc::{self, Rpc};
-use ethane::types::{Bytes, PrivateKey, TransactionRequest, H160, H256, U256};
+use ethane::storage::StorageLayout;
+use ethane::types::{Bytes, PrivateKey, TransactionRequest, H160, H256};
+use ethane::units::parse_units;
 
 use rand::Rng;
 use serde::de::DeserializeOwned;
@@ -8,7 +10,6 @@ use std::fmt::Debug;
 use std::path::Path;
 use std::process::Command;
 use std::str::FromStr;
-use tiny_keccak::{Hasher, Keccak};
 
 mod spin_up;
 pub use spin_up::{ConnectorNodeBundle, ConnectorWrapper, NodeProcess};
@@ -65,7 +66,7 @@ pub fn prefund_account(client: &mut ConnectorWrapper, address: H160) -> H256 {
     let tx = TransactionRequest {
         from: coinbase,
         to: Some(address),
-        value: Some(U256::exp10(20)),
+        value: Some(parse_units("100", "ether").unwrap()),
         ..Default::default()
     };
     let tx_hash = client.call(rpc::eth_send_transaction(tx)).unwrap();
@@ -84,7 +85,12 @@ pub fn create_account(client: &mut ConnectorWrapper) -> (H256, H160) {
 pub fn compile_contract(path: &Path, contract_name: &str) -> Value {
     let path_as_str = path.to_str().unwrap();
     let output = Command::new("solc")
-        .args(&[path_as_str, "--optimize", "--combined-json", "abi,bin"])
+        .args(&[
+            path_as_str,
+            "--optimize",
+            "--combined-json",
+            "abi,bin,storage-layout",
+        ])
         .output()
         .expect("Failed to compile contract. Is Solidity compiler solc installed?")
         .stdout;
@@ -127,12 +133,13 @@ pub fn abi(contract_input: Value) -> Value {
     contract_input["abi"].clone()
 }
 
+pub fn storage_layout(contract_input: Value) -> StorageLayout {
+    serde_json::from_value(contract_input["storage-layout"].clone())
+        .expect("Failed to deserialize storage layout")
+}
+
 pub fn keccak(input: &[u8]) -> [u8; 32] {
-    let mut hasher = Keccak::v256();
-    hasher.update(input);
-    let mut out = [0u8; 32];
-    hasher.finalize(&mut out);
-    out
+    ethane::utils::keccak256(input)
 }
 
 pub fn rpc_call_test_expected<T: DeserializeOwned + Debug + PartialEq>(
@@ -46,6 +46,18 @@ impl ConnectorWrapper {
         }
     }
 
+    pub fn call_batch<U: DeserializeOwned + Debug>(
+        &mut self,
+        rpcs: Vec<Rpc<U>>,
+    ) -> Result<Vec<Result<U, ConnectorError>>, ConnectorError> {
+        match self {
+            Self::Websocket(connector) => connector.connector.call_batch(rpcs),
+            Self::Http(connector) => connector.connector.call_batch(rpcs),
+            #[cfg(target_family = "unix")]
+            Self::Uds(connector) => connector.connector.call_batch(rpcs),
+        }
+    }
+
     pub fn subscribe<U: DeserializeOwned + Debug + 'static>(
         &mut self,
         sub_request: SubscriptionRequest<U>,
@@ -59,6 +71,31 @@ impl ConnectorWrapper {
     }
 }
 
+/// Evaluates `$body` with `$connector` bound to the connector of a [ConnectorWrapper]
+///
+/// This allows to use functions which are generic over the transport, e.g.
+/// `with_connector!(client, |connector| Erc20::new(token).decimals(connector, None))`.
+#[macro_export]
+macro_rules! with_connector {
+    ($client:expr, |$connector:ident| $body:expr) => {
+        match &mut $client {
+            $crate::ConnectorWrapper::Websocket(bundle) => {
+                let $connector = bundle.connector_mut();
+                $body
+            }
+            $crate::ConnectorWrapper::Http(bundle) => {
+                let $connector = bundle.connector_mut();
+                $body
+            }
+            #[cfg(target_family = "unix")]
+            $crate::ConnectorWrapper::Uds(bundle) => {
+                let $connector = bundle.connector_mut();
+                $body
+            }
+        }
+    };
+}
+
 pub trait DynSubscription<T: DeserializeOwned + Debug> {
     fn next_item(&mut self) -> Result<T, SubscriptionError>;
 }
@@ -81,6 +118,10 @@ impl<T: Request> ConnectorNodeBundle<T> {
     fn call<U: DeserializeOwned + Debug>(&mut self, rpc: Rpc<U>) -> Result<U, ConnectorError> {
         self.connector.call(rpc)
     }
+
+    pub fn connector_mut(&mut self) -> &mut Connector<T> {
+        &mut self.connector
+    }
 }
 
 impl<T: Subscribe + Request + 'static> ConnectorNodeBundle<T> {
@@ -131,7 +172,7 @@ impl NodeProcess {
         let cmd = vec![
             "--http".to_string(),
             "--http.api".to_string(),
-            "personal,eth,net,web3,txpool".to_string(),
+            "personal,eth,net,web3,txpool,debug,admin,miner".to_string(),
             "--http.port".to_string(),
             port.to_string(),
             "--allow-insecure-unlock".to_string(),
@@ -147,7 +188,7 @@ impl NodeProcess {
         let cmd = vec![
             "--ws".to_string(),
             "--ws.api".to_string(),
-            "personal,eth,net,web3,txpool".to_string(),
+            "personal,eth,net,web3,txpool,debug,admin,miner".to_string(),
             "--ws.port".to_string(),
             port.to_string(),
             "--allow-insecure-unlock".to_string(),
@@ -0,0 +1,29 @@
+use ethane::rpc;
+
+use test_helper::*;
+
+#[test]
+fn test_admin_node_info() {
+    let mut client = ConnectorWrapper::new_from_env();
+    rpc_call_test_some(&mut client, rpc::admin_node_info());
+}
+
+#[test]
+fn test_admin_peers() {
+    let mut client = ConnectorWrapper::new_from_env();
+    rpc_call_test_expected(&mut client, rpc::admin_peers(), Vec::new());
+}
+
+#[test]
+fn test_admin_add_and_remove_peer() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let enode = client.call(rpc::admin_node_info()).unwrap().enode;
+    rpc_call_test_expected(&mut client, rpc::admin_add_peer(enode.clone()), true);
+    rpc_call_test_expected(&mut client, rpc::admin_remove_peer(enode), true);
+}
+
+#[test]
+fn test_admin_datadir() {
+    let mut client = ConnectorWrapper::new_from_env();
+    rpc_call_test_some(&mut client, rpc::admin_datadir());
+}
@@ -0,0 +1,122 @@
+use ethane::rpc;
+use ethane::types::{
+    BlockParameter, Bytes, Call, TraceOptions, TraceResult, Tracer, TransactionRequest, U256,
+};
+use std::path::Path;
+
+use test_helper::*;
+
+#[test]
+fn test_debug_trace_transaction() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let transaction = TransactionRequest {
+        from: create_account(&mut client).1,
+        to: Some(create_account(&mut client).1),
+        value: Some(U256::zero()),
+        ..Default::default()
+    };
+    let tx_hash = client.call(rpc::eth_send_transaction(transaction)).unwrap();
+    wait_for_transaction(&mut client, tx_hash);
+    let trace = client
+        .call(rpc::debug_trace_transaction(tx_hash, None))
+        .unwrap();
+    assert!(matches!(trace, TraceResult::StructLogs(_)));
+}
+
+#[test]
+fn test_debug_trace_transaction_call_tracer() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let transaction = TransactionRequest {
+        from: create_account(&mut client).1,
+        to: Some(create_account(&mut client).1),
+        value: Some(U256::zero()),
+        ..Default::default()
+    };
+    let tx_hash = client.call(rpc::eth_send_transaction(transaction)).unwrap();
+    wait_for_transaction(&mut client, tx_hash);
+    let options = TraceOptions {
+        tracer: Some(Tracer::CallTracer),
+        ..Default::default()
+    };
+    let trace = client
+        .call(rpc::debug_trace_transaction(tx_hash, Some(options)))
+        .unwrap();
+    assert!(matches!(trace, TraceResult::CallFrame(_)));
+}
+
+#[test]
+fn test_debug_trace_call() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let address = create_account(&mut client).1;
+    let (contract_address, _) = deploy_contract(
+        &mut client,
+        address,
+        Path::new(TEST_CONTRACT_PATH),
+        TEST_CONTRACT_NAME,
+    );
+    let out = keccak(b"solution()");
+    let call = Call {
+        to: contract_address,
+        data: Some(Bytes::from_slice(&out[..4])),
+        ..Default::default()
+    };
+    let options = TraceOptions {
+        tracer: Some(Tracer::PrestateTracer),
+        ..Default::default()
+    };
+    let trace = client
+        .call(rpc::debug_trace_call(call, None, Some(options)))
+        .unwrap();
+    assert!(matches!(trace, TraceResult::Prestate(_)));
+}
+
+#[test]
+fn test_debug_trace_block_by_number() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let transaction = TransactionRequest {
+        from: create_account(&mut client).1,
+        to: Some(create_account(&mut client).1),
+        value: Some(U256::zero()),
+        ..Default::default()
+    };
+    let tx_hash = client.call(rpc::eth_send_transaction(transaction)).unwrap();
+    wait_for_transaction(&mut client, tx_hash);
+    let block_number = client
+        .call(rpc::eth_get_transaction_by_hash(tx_hash))
+        .unwrap()
+        .block_number
+        .unwrap();
+    let options = TraceOptions {
+        tracer: Some(Tracer::CallTracer),
+        ..Default::default()
+    };
+    let traces = client
+        .call(rpc::debug_trace_block_by_number(
+            Some(BlockParameter::Custom(block_number)),
+            Some(options),
+        ))
+        .unwrap();
+    assert!(!traces.is_empty());
+}
+
+#[test]
+fn test_debug_trace_block_by_hash() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let transaction = TransactionRequest {
+        from: create_account(&mut client).1,
+        to: Some(create_account(&mut client).1),
+        value: Some(U256::zero()),
+        ..Default::default()
+    };
+    let tx_hash = client.call(rpc::eth_send_transaction(transaction)).unwrap();
+    wait_for_transaction(&mut client, tx_hash);
+    let block_hash = client
+        .call(rpc::eth_get_transaction_by_hash(tx_hash))
+        .unwrap()
+        .block_hash
+        .unwrap();
+    rpc_call_test_some(
+        &mut client,
+        rpc::debug_trace_block_by_hash(block_hash, None),
+    );
+}
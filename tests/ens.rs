@@ -0,0 +1,114 @@
+use ethane::abi::{encode_call, Token};
+use ethane::ens::{namehash, Ens, EnsError};
+use ethane::rpc;
+use ethane::types::{TransactionRequest, H160};
+use ethane::utils::selector;
+use std::path::Path;
+
+use test_helper::*;
+
+fn send(client: &mut ConnectorWrapper, from: H160, to: H160, signature: &str, tokens: &[Token]) {
+    let transaction = TransactionRequest {
+        from,
+        to: Some(to),
+        data: Some(encode_call(selector(signature), tokens)),
+        ..Default::default()
+    };
+    let tx_hash = client.call(rpc::eth_send_transaction(transaction)).unwrap();
+    wait_for_transaction(client, tx_hash);
+}
+
+fn node(name: &str) -> Token {
+    Token::FixedBytes(namehash(name).unwrap().as_bytes().to_vec())
+}
+
+#[test]
+fn test_ens_resolve_with_local_registry() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let address = create_account(&mut client).1;
+    let path = Path::new(TEST_ENS_PATH);
+    let (registry, _) = deploy_contract(&mut client, address, path, TEST_ENS_REGISTRY_NAME);
+    let (resolver, _) = deploy_contract(&mut client, address, path, TEST_ENS_RESOLVER_NAME);
+    let reverse_name = hex::encode(address.as_bytes()) + ".addr.reverse";
+
+    for name in ["ethane.eth", "unset.eth", reverse_name.as_str()] {
+        send(
+            &mut client,
+            address,
+            registry,
+            "setResolver(bytes32,address)",
+            &[node(name), Token::Address(resolver)],
+        );
+    }
+    send(
+        &mut client,
+        address,
+        resolver,
+        "setAddr(bytes32,address)",
+        &[node("ethane.eth"), Token::Address(address)],
+    );
+    send(
+        &mut client,
+        address,
+        resolver,
+        "setText(bytes32,string,string)",
+        &[
+            node("ethane.eth"),
+            Token::String(String::from("url")),
+            Token::String(String::from("https://github.com/th4s/ethane")),
+        ],
+    );
+    send(
+        &mut client,
+        address,
+        resolver,
+        "setName(bytes32,string)",
+        &[
+            node(&reverse_name),
+            Token::String(String::from("ethane.eth")),
+        ],
+    );
+
+    let ens = Ens::new(Some(registry));
+    assert_eq!(
+        with_connector!(client, |connector| ens.resolver(
+            connector,
+            "ethane.eth",
+            None
+        ))
+        .unwrap(),
+        resolver
+    );
+    assert_eq!(
+        with_connector!(client, |connector| ens.address(
+            connector,
+            "ethane.eth",
+            None
+        ))
+        .unwrap(),
+        address
+    );
+    assert_eq!(
+        with_connector!(client, |connector| ens.text(
+            connector,
+            "ethane.eth",
+            "url",
+            None
+        ))
+        .unwrap(),
+        "https://github.com/th4s/ethane"
+    );
+    assert_eq!(
+        with_connector!(client, |connector| ens.name(connector, address, None)).unwrap(),
+        "ethane.eth"
+    );
+
+    assert!(matches!(
+        with_connector!(client, |connector| ens.address(connector, "unset.eth", None)),
+        Err(EnsError::NoAddress(name)) if name == "unset.eth"
+    ));
+    assert!(matches!(
+        with_connector!(client, |connector| ens.address(connector, "missing.eth", None)),
+        Err(EnsError::NoResolver(name)) if name == "missing.eth"
+    ));
+}
@@ -1,6 +1,10 @@
+use ethane::deploy;
+use ethane::eip712;
+use ethane::proof;
 use ethane::rpc;
 use ethane::types::{
-    BlockParameter, Bytes, Call, Filter, GasCall, TransactionRequest, ValueOrVec, H256, U256, U64,
+    AccountOverride, BlockId, BlockOverrides, BlockParameter, Bytes, Call, Filter, GasCall,
+    PrivateKey, StateOverride, TransactionRequest, TypedData, ValueOrVec, H160, H256, U256, U64,
 };
 use std::path::Path;
 use std::str::FromStr;
@@ -43,6 +47,12 @@ fn test_eth_gas_price() {
     rpc_call_test_some(&mut client, rpc::eth_gas_price());
 }
 
+#[test]
+fn test_eth_blob_base_fee() {
+    let mut client = ConnectorWrapper::new_from_env();
+    rpc_call_test_some(&mut client, rpc::eth_blob_base_fee());
+}
+
 #[test]
 fn test_eth_accounts() {
     let mut client = ConnectorWrapper::new_from_env();
@@ -66,6 +76,29 @@ fn test_eth_get_balance() {
     );
 }
 
+#[test]
+fn test_eth_get_balance_by_block_hash() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let (_secret, address) = create_account(&mut client);
+    let block_hash = client
+        .call(rpc::eth_get_block_by_number(None, false))
+        .unwrap()
+        .unwrap()
+        .hash
+        .unwrap();
+    rpc_call_test_expected(
+        &mut client,
+        rpc::eth_get_balance(
+            address,
+            Some(BlockId::Hash {
+                hash: block_hash,
+                require_canonical: true,
+            }),
+        ),
+        U256::exp10(20),
+    );
+}
+
 #[test]
 fn test_eth_send_transaction_to_address() {
     let mut client = ConnectorWrapper::new_from_env();
@@ -147,6 +180,55 @@ fn test_eth_get_storage_at() {
     );
 }
 
+#[test]
+fn test_eth_get_proof() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let address = create_account(&mut client).1;
+    let (contract_address, _) = deploy_contract(
+        &mut client,
+        address,
+        &Path::new(TEST_CONTRACT_PATH),
+        TEST_CONTRACT_NAME,
+    );
+    let block = client
+        .call(rpc::eth_get_block_by_number(None, false))
+        .unwrap()
+        .unwrap();
+    let block_id = Some(BlockId::from(BlockParameter::Custom(block.number.unwrap())));
+    let account_proof = client
+        .call(rpc::eth_get_proof(
+            contract_address,
+            vec![U256::zero(), U256::one()],
+            block_id,
+        ))
+        .unwrap();
+    let code = client
+        .call(rpc::eth_get_code(contract_address, block_id))
+        .unwrap();
+
+    assert_eq!(account_proof.storage_proof[0].value, U256::from(11));
+    assert!(proof::verify_proof(block.state_root, &account_proof).is_ok());
+    assert!(proof::verify_code(&account_proof, &code).is_ok());
+}
+
+#[test]
+fn test_eth_get_proof_of_eoa() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let address = create_account(&mut client).1;
+    let block = client
+        .call(rpc::eth_get_block_by_number(None, false))
+        .unwrap()
+        .unwrap();
+    let block_id = Some(BlockId::from(BlockParameter::Custom(block.number.unwrap())));
+    let account_proof = client
+        .call(rpc::eth_get_proof(address, vec![U256::zero()], block_id))
+        .unwrap();
+
+    assert_eq!(account_proof.storage_hash, proof::EMPTY_TRIE_ROOT);
+    assert!(account_proof.storage_proof[0].proof.is_empty());
+    assert!(proof::verify_proof(block.state_root, &account_proof).is_ok());
+}
+
 #[test]
 fn test_eth_get_transaction_count() {
     let mut client = ConnectorWrapper::new_from_env();
@@ -189,6 +271,34 @@ fn test_eth_get_block_by_number_full_tx() {
     rpc_call_test_some(&mut client, rpc::eth_get_block_by_number(None, true));
 }
 
+#[test]
+fn test_eth_get_block_by_number_verify_hash() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let sender = create_account(&mut client).1;
+    let transaction = TransactionRequest {
+        from: sender,
+        to: Some(create_account(&mut client).1),
+        value: Some(U256::zero()),
+        ..Default::default()
+    };
+    let tx_hash = client.call(rpc::eth_send_transaction(transaction)).unwrap();
+    wait_for_transaction(&mut client, tx_hash);
+    let block = client
+        .call(rpc::eth_get_block_by_number(None, false))
+        .unwrap()
+        .unwrap();
+    assert!(block.verify_hash().is_ok());
+}
+
+#[test]
+fn test_eth_get_block_by_number_finalized() {
+    let mut client = ConnectorWrapper::new_from_env();
+    rpc_call_test_some(
+        &mut client,
+        rpc::eth_get_block_by_number(Some(BlockParameter::Finalized), false),
+    );
+}
+
 #[test]
 fn test_eth_get_block_by_number_only_hashes() {
     let mut client = ConnectorWrapper::new_from_env();
@@ -317,6 +427,25 @@ fn test_eth_get_code_contract() {
     rpc_call_test_some(&mut client, rpc::eth_get_code(contract_address, None));
 }
 
+#[test]
+fn test_eth_deploy_create_address() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let address = create_account(&mut client).1;
+    let nonce = client
+        .call(rpc::eth_get_transaction_count(
+            address,
+            Some(BlockParameter::Pending.into()),
+        ))
+        .unwrap();
+    let (contract_address, _) = deploy_contract(
+        &mut client,
+        address,
+        &Path::new(TEST_CONTRACT_PATH),
+        TEST_CONTRACT_NAME,
+    );
+    assert_eq!(deploy::create_address(address, nonce), contract_address);
+}
+
 #[test]
 fn test_eth_sign() {
     let mut client = ConnectorWrapper::new_from_env();
@@ -342,6 +471,102 @@ fn test_eth_sign() {
     );
 }
 
+// eth_signTypedData_v4 is not provided by geth, run this against a node or wallet which supports it
+#[test]
+#[ignore]
+fn test_eth_sign_typed_data_v4() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let private_key = PrivateKey::NonPrefixed(H256::from_str(FIX_SECRET).unwrap());
+    let address = import_account(&mut client, H256::from_str(FIX_SECRET).unwrap());
+    client
+        .call(rpc::personal_unlock_account(
+            address,
+            String::from(ACCOUNTS_PASSWORD),
+            None,
+        ))
+        .unwrap();
+    let typed_data: TypedData = serde_json::from_str(
+        r#"{
+            "types": {
+                "Person": [
+                    {"name": "name", "type": "string"},
+                    {"name": "wallet", "type": "address"}
+                ]
+            },
+            "primaryType": "Person",
+            "domain": {"name": "ethane", "chainId": 1337},
+            "message": {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"}
+        }"#,
+    )
+    .unwrap();
+    let expected = eip712::sign_typed_data(&typed_data, &private_key)
+        .unwrap()
+        .to_bytes()
+        .unwrap();
+
+    rpc_call_test_expected(
+        &mut client,
+        rpc::eth_sign_typed_data_v4(address, typed_data),
+        expected,
+    );
+}
+
+// Signs the typed data as sent to the node and compares it with the signature of the EIP-712 example
+#[test]
+fn test_eth_sign_typed_data_v4_known_signature() {
+    let typed_data: TypedData = serde_json::from_str(
+        r#"{
+            "types": {
+                "EIP712Domain": [
+                    {"name": "name", "type": "string"},
+                    {"name": "version", "type": "string"},
+                    {"name": "chainId", "type": "uint256"},
+                    {"name": "verifyingContract", "type": "address"}
+                ],
+                "Person": [
+                    {"name": "name", "type": "string"},
+                    {"name": "wallet", "type": "address"}
+                ],
+                "Mail": [
+                    {"name": "from", "type": "Person"},
+                    {"name": "to", "type": "Person"},
+                    {"name": "contents", "type": "string"}
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+            },
+            "message": {
+                "from": {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},
+                "to": {"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},
+                "contents": "Hello, Bob!"
+            }
+        }"#,
+    )
+    .unwrap();
+    let private_key = PrivateKey::NonPrefixed(H256::from(keccak(b"cow")));
+    let address = H160::from_str("0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826").unwrap();
+    let rpc = rpc::eth_sign_typed_data_v4(address, typed_data);
+    let sent: TypedData = serde_json::from_value(rpc.params[1].clone()).unwrap();
+
+    let signature = eip712::sign_typed_data(&sent, &private_key).unwrap();
+    assert_eq!(
+        signature.r,
+        H256::from_str("0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d")
+            .unwrap()
+    );
+    assert_eq!(
+        signature.s,
+        H256::from_str("0x07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562")
+            .unwrap()
+    );
+    assert_eq!(signature.v, 28);
+}
+
 // DEVIATION FROM SPEC
 // c.f. https://github.com/ethereum/go-ethereum/issues/22223
 // also geth returns something like: {raw: hex_encoded_tx, tx: json_encoded_tx}, however according to JSON RPC
@@ -400,11 +625,81 @@ fn test_eth_call() {
 
     rpc_call_test_expected(
         &mut client,
-        rpc::eth_call(call, None),
+        rpc::eth_call(call, None, None, None),
         Bytes::from_slice(&expected),
     );
 }
 
+#[test]
+fn test_eth_call_state_override() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let address = create_account(&mut client).1;
+    let (contract_address, _) = deploy_contract(
+        &mut client,
+        address,
+        &Path::new(TEST_CONTRACT_PATH),
+        TEST_CONTRACT_NAME,
+    );
+    // Runtime code which returns the first storage slot
+    let code = Bytes::from_str("0x60005460005260206000f3").unwrap();
+    let mut state_override = StateOverride::new();
+    state_override.insert(
+        contract_address,
+        AccountOverride {
+            code: Some(code),
+            state_diff: Some(
+                vec![(H256::zero(), H256::from_low_u64_be(7))]
+                    .into_iter()
+                    .collect(),
+            ),
+            ..Default::default()
+        },
+    );
+    let block_overrides = BlockOverrides {
+        number: Some(U64::from(1_000_000)),
+        ..Default::default()
+    };
+    let call = Call {
+        to: contract_address,
+        ..Default::default()
+    };
+
+    rpc_call_test_expected(
+        &mut client,
+        rpc::eth_call(call, None, Some(state_override), Some(block_overrides)),
+        Bytes::from_slice(H256::from_low_u64_be(7).as_bytes()),
+    );
+}
+
+#[test]
+fn test_eth_create_access_list() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let address = create_account(&mut client).1;
+    let (contract_address, _) = deploy_contract(
+        &mut client,
+        address,
+        &Path::new(TEST_CONTRACT_PATH),
+        TEST_CONTRACT_NAME,
+    );
+    let out = keccak(b"solution()");
+    let transaction = TransactionRequest {
+        from: address,
+        to: Some(contract_address),
+        data: Some(Bytes::from_slice(&out[..4])),
+        ..Default::default()
+    };
+    let result = client
+        .call(rpc::eth_create_access_list(transaction.clone(), None))
+        .unwrap();
+    assert_eq!(result.error, None);
+    assert!(result.gas_used > U256::zero());
+
+    rpc_call_test_some(
+        &mut client,
+        rpc::eth_send_transaction(transaction.with_access_list(result)),
+    );
+}
+
 #[test]
 fn test_eth_estimate_gas() {
     let mut client = ConnectorWrapper::new_from_env();
@@ -416,7 +711,33 @@ fn test_eth_estimate_gas() {
     };
     rpc_call_test_expected(
         &mut client,
-        rpc::eth_estimate_gas(gas_call, None),
+        rpc::eth_estimate_gas(gas_call, None, None),
+        U256::from(21000),
+    );
+}
+
+#[test]
+fn test_eth_estimate_gas_state_override() {
+    let mut client = ConnectorWrapper::new_from_env();
+    // An account without funds can only transfer value with an overridden balance
+    let sender = H160::from_low_u64_be(0xe7a);
+    let mut state_override = StateOverride::new();
+    state_override.insert(
+        sender,
+        AccountOverride {
+            balance: Some(U256::exp10(20)),
+            ..Default::default()
+        },
+    );
+    let gas_call = GasCall {
+        from: Some(sender),
+        to: Some(create_account(&mut client).1),
+        value: Some(U256::exp10(18)),
+        ..Default::default()
+    };
+    rpc_call_test_expected(
+        &mut client,
+        rpc::eth_estimate_gas(gas_call, None, Some(state_override)),
         U256::from(21000),
     );
 }
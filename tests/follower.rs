@@ -0,0 +1,51 @@
+use ethane::follower::{ChainEvent, ChainFollower};
+use ethane::rpc;
+use ethane::types::{Bytes, FilterSubscription, TransactionRequest, ValueOrVec, H256};
+use std::path::Path;
+
+use test_helper::*;
+
+#[test]
+fn test_follower_poll() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let address = create_account(&mut client).1;
+    let (contract_address, _) = deploy_contract(
+        &mut client,
+        address,
+        Path::new(TEST_CONTRACT_PATH),
+        TEST_CONTRACT_NAME,
+    );
+    let tx = TransactionRequest {
+        from: address,
+        to: Some(contract_address),
+        data: Some(Bytes::from_slice(&keccak(b"set_pos0()")[..4])),
+        ..Default::default()
+    };
+    let tx_hash = client.call(rpc::eth_send_transaction(tx)).unwrap();
+    wait_for_transaction(&mut client, tx_hash);
+
+    let filter = FilterSubscription {
+        address: Some(ValueOrVec::Value(contract_address)),
+        topics: Some(vec![Some(ValueOrVec::Value(H256::from(keccak(
+            b"Solution(uint256)",
+        ))))]),
+    };
+    with_connector!(client, |connector| {
+        let mut follower = ChainFollower::new(connector, Some(filter));
+        let mut events = follower.poll().unwrap();
+        assert_eq!(events.len(), 1);
+        let (header, logs) = match events.remove(0) {
+            ChainEvent::Applied { header, logs } => (header, logs),
+            event => panic!("Unexpected event {:?}", event),
+        };
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].transaction_hash, Some(tx_hash));
+        assert_eq!(logs[0].block_hash, header.hash);
+        assert!(!logs[0].removed);
+
+        // The head is already known
+        assert!(follower.poll().unwrap().is_empty());
+        assert!(follower.process(header.clone()).unwrap().is_empty());
+        assert_eq!(follower.head(), Some(&header));
+    });
+}
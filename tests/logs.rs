@@ -0,0 +1,50 @@
+use ethane::logs::LogScanner;
+use ethane::rpc;
+use ethane::types::{BlockParameter, Bytes, Filter, TransactionRequest, ValueOrVec, H256};
+use std::path::Path;
+
+use test_helper::*;
+
+#[test]
+fn test_logs_scan() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let address = create_account(&mut client).1;
+    let (contract_address, _) = deploy_contract(
+        &mut client,
+        address,
+        Path::new(TEST_CONTRACT_PATH),
+        TEST_CONTRACT_NAME,
+    );
+    let mut transaction_hashes = Vec::new();
+    for _ in 0..3 {
+        let tx = TransactionRequest {
+            from: address,
+            to: Some(contract_address),
+            data: Some(Bytes::from_slice(&keccak(b"set_pos0()")[..4])),
+            ..Default::default()
+        };
+        let tx_hash = client.call(rpc::eth_send_transaction(tx)).unwrap();
+        wait_for_transaction(&mut client, tx_hash);
+        transaction_hashes.push(tx_hash);
+    }
+
+    let filter = Filter {
+        from_block: Some(BlockParameter::Earliest),
+        to_block: Some(BlockParameter::Latest),
+        address: Some(ValueOrVec::Value(contract_address)),
+        topics: Some(vec![Some(ValueOrVec::Value(H256::from(keccak(
+            b"Solution(uint256)",
+        ))))]),
+    };
+    let logs = with_connector!(client, |connector| LogScanner::new(connector, filter)
+        .unwrap()
+        .with_chunk_size(1)
+        .collect::<Result<Vec<_>, _>>())
+    .unwrap();
+    assert_eq!(
+        logs.iter()
+            .map(|log| log.transaction_hash.unwrap())
+            .collect::<Vec<H256>>(),
+        transaction_hashes
+    );
+}
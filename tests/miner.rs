@@ -0,0 +1,34 @@
+use ethane::rpc;
+use ethane::types::U256;
+
+use test_helper::*;
+
+#[test]
+fn test_miner_start_stop() {
+    let mut client = ConnectorWrapper::new_from_env();
+    rpc_call_test_expected(&mut client, rpc::miner_stop(), ());
+    rpc_call_test_expected(&mut client, rpc::miner_start(None), ());
+}
+
+#[test]
+fn test_miner_set_etherbase() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let coinbase = client.call(rpc::eth_coinbase()).unwrap();
+    rpc_call_test_expected(&mut client, rpc::miner_set_etherbase(coinbase), true);
+}
+
+#[test]
+fn test_miner_set_gas_price() {
+    let mut client = ConnectorWrapper::new_from_env();
+    rpc_call_test_expected(&mut client, rpc::miner_set_gas_price(U256::exp10(9)), true);
+}
+
+#[test]
+fn test_miner_set_extra() {
+    let mut client = ConnectorWrapper::new_from_env();
+    rpc_call_test_expected(
+        &mut client,
+        rpc::miner_set_extra(String::from("ethane")),
+        true,
+    );
+}
@@ -0,0 +1,102 @@
+use ethane::multicall::{Multicall, MulticallResult};
+use ethane::rpc;
+use ethane::types::{Bytes, Call, H160, U256};
+use std::path::Path;
+
+use test_helper::*;
+
+#[test]
+fn test_call_batch() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let address = create_account(&mut client).1;
+    let results = client
+        .call_batch(vec![
+            rpc::eth_get_balance(address, None),
+            rpc::eth_get_balance(H160::zero(), None),
+        ])
+        .unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_ref().unwrap(), &U256::exp10(20));
+}
+
+#[test]
+fn test_multicall_without_contract_uses_batch() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let address = create_account(&mut client).1;
+    let (contract_address, _) = deploy_contract(
+        &mut client,
+        address,
+        Path::new(TEST_CONTRACT_PATH),
+        TEST_CONTRACT_NAME,
+    );
+    let solution = Bytes::from_slice(&keccak(b"solution()")[..4]);
+    let mut multicall = Multicall::default();
+    for _ in 0..3 {
+        multicall.add_call(Call {
+            to: contract_address,
+            data: Some(solution.clone()),
+            ..Default::default()
+        });
+    }
+    let mut expected = [0u8; 32];
+    expected[31] = 42;
+
+    let results = with_connector!(client, |connector| multicall.call(connector, None)).unwrap();
+    assert_eq!(
+        results,
+        vec![
+            MulticallResult {
+                success: true,
+                return_data: Bytes::from_slice(&expected),
+            };
+            3
+        ]
+    );
+}
+
+#[test]
+fn test_multicall_aggregate3() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let address = create_account(&mut client).1;
+    let (contract_address, _) = deploy_contract(
+        &mut client,
+        address,
+        Path::new(TEST_CONTRACT_PATH),
+        TEST_CONTRACT_NAME,
+    );
+    let (multicall_address, _) = deploy_contract(
+        &mut client,
+        address,
+        Path::new(TEST_MULTICALL_PATH),
+        TEST_MULTICALL_NAME,
+    );
+    let mut multicall = Multicall::new(Some(multicall_address));
+    multicall.add_call(Call {
+        to: contract_address,
+        data: Some(Bytes::from_slice(&keccak(b"solution()")[..4])),
+        ..Default::default()
+    });
+    // Unknown selector, which reverts
+    multicall.add_call(Call {
+        to: contract_address,
+        data: Some(Bytes::from_slice(&keccak(b"missing()")[..4])),
+        ..Default::default()
+    });
+    let mut expected = [0u8; 32];
+    expected[31] = 42;
+
+    let results = with_connector!(client, |connector| multicall.call(connector, None)).unwrap();
+    assert_eq!(
+        results,
+        vec![
+            MulticallResult {
+                success: true,
+                return_data: Bytes::from_slice(&expected),
+            },
+            MulticallResult {
+                success: false,
+                return_data: Bytes::default(),
+            },
+        ]
+    );
+}
@@ -1,5 +1,6 @@
 use ethane::rpc;
-use ethane::types::{Bytes, PrivateKey, TransactionRequest, H160, H256};
+use ethane::signing;
+use ethane::types::{Address, Bytes, PrivateKey, TransactionRequest, H160, H256};
 use std::str::FromStr;
 
 use test_helper::*;
@@ -22,6 +23,12 @@ fn test_personal_import_raw_key() {
     )
 }
 
+#[test]
+fn test_personal_import_raw_key_checksum() {
+    let address = Address::from(H160::from_str(FIX_ADDRESS).unwrap());
+    assert_eq!(address.to_string(), FIX_ADDRESS);
+}
+
 #[test]
 fn test_personal_unlock_account() {
     let mut client = ConnectorWrapper::new_from_env();
@@ -101,3 +108,18 @@ fn test_personal_ec_recover() {
         H160::from_str(FIX_ADDRESS).unwrap(),
     )
 }
+
+#[test]
+fn test_personal_ec_recover_local_signature() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let private_key = PrivateKey::NonPrefixed(H256::from_str(FIX_SECRET).unwrap());
+    let signature = signing::sign_message(b"checkmate", &private_key).unwrap();
+    rpc_call_test_expected(
+        &mut client,
+        rpc::personal_ec_recover(
+            Bytes::from_slice(b"checkmate"),
+            signature.to_bytes().unwrap(),
+        ),
+        H160::from_str(FIX_ADDRESS).unwrap(),
+    )
+}
@@ -0,0 +1,88 @@
+use ethane::rpc;
+use ethane::storage::StorageValue;
+use ethane::types::{Bytes, TransactionRequest, U256};
+use std::path::Path;
+
+use test_helper::*;
+
+#[test]
+fn test_storage_read_layout() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let address = create_account(&mut client).1;
+    let path = Path::new(TEST_CONTRACT_PATH);
+    let layout = storage_layout(compile_contract(path, TEST_CONTRACT_NAME));
+    let (contract_address, _) = deploy_contract(&mut client, address, path, TEST_CONTRACT_NAME);
+    assert_eq!(
+        with_connector!(client, |connector| layout.read(
+            connector,
+            contract_address,
+            "pos0",
+            None
+        ))
+        .unwrap(),
+        StorageValue::Uint(U256::from(11))
+    );
+
+    let tx = TransactionRequest {
+        from: address,
+        to: Some(contract_address),
+        data: Some(Bytes::from_slice(&keccak(b"set_pos0()")[..4])),
+        ..Default::default()
+    };
+    let tx_hash = client.call(rpc::eth_send_transaction(tx)).unwrap();
+    wait_for_transaction(&mut client, tx_hash);
+    assert_eq!(
+        with_connector!(client, |connector| layout.read(
+            connector,
+            contract_address,
+            "pos0",
+            None
+        ))
+        .unwrap(),
+        StorageValue::Uint(U256::from(2))
+    );
+}
+
+#[test]
+fn test_storage_read_fixture() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let address = create_account(&mut client).1;
+    let path = Path::new(TEST_STORAGE_PATH);
+    let layout = storage_layout(compile_contract(path, TEST_STORAGE_NAME));
+    let (contract_address, _) = deploy_contract(&mut client, address, path, TEST_STORAGE_NAME);
+    let account = format!("balances[{:?}]", address);
+    let expected = [
+        (String::from("small"), StorageValue::Uint(U256::from(7))),
+        (String::from("flag"), StorageValue::Bool(true)),
+        (String::from("admin"), StorageValue::Address(address)),
+        (
+            account.clone() + ".amount",
+            StorageValue::Uint(U256::from(100)),
+        ),
+        (
+            account.clone() + ".since",
+            StorageValue::Uint(U256::from(12)),
+        ),
+        (account.clone() + ".frozen", StorageValue::Bool(true)),
+        (account + ".owner", StorageValue::Address(address)),
+        (
+            String::from("name"),
+            StorageValue::String(String::from(
+                "A name which does not fit into a single storage slot",
+            )),
+        ),
+    ];
+
+    for (path, value) in expected {
+        assert_eq!(
+            with_connector!(client, |connector| layout.read(
+                connector,
+                contract_address,
+                &path,
+                None
+            ))
+            .unwrap(),
+            value
+        );
+    }
+}
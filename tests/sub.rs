@@ -1,10 +1,11 @@
 use ethane::rpc::eth_send_transaction;
 use ethane::rpc::sub::{
     eth_subscribe_logs, eth_subscribe_new_heads, eth_subscribe_new_pending_transactions,
-    eth_subscribe_syncing,
+    eth_subscribe_new_pending_transactions_full, eth_subscribe_syncing,
 };
 use ethane::types::{
-    BlockHeader, Bytes, FilterSubscription, Log, TransactionRequest, ValueOrVec, H256, U256,
+    BlockHeader, Bytes, FilterSubscription, Log, Transaction, TransactionRequest, ValueOrVec, H256,
+    U256,
 };
 use std::path::Path;
 
@@ -24,7 +25,9 @@ fn test_eth_subscribe_new_heads() {
         };
         let tx_hash = client.call(eth_send_transaction(transaction)).unwrap();
         wait_for_transaction(&mut client, tx_hash);
-        blocks.push(subscription.next_item().unwrap());
+        let header = subscription.next_item().unwrap();
+        assert!(header.verify_hash().is_ok());
+        blocks.push(header);
         if blocks.len() >= 2 {
             break assert!(true);
         }
@@ -54,6 +57,29 @@ fn test_eth_subscribe_new_pending_transactions() {
     }
 }
 
+#[test]
+fn test_eth_subscribe_new_pending_transactions_full() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let mut subscription = client
+        .subscribe(eth_subscribe_new_pending_transactions_full())
+        .unwrap();
+    let mut transactions = Vec::<Transaction>::new();
+    loop {
+        let transaction = TransactionRequest {
+            from: create_account(&mut client).1,
+            to: Some(create_account(&mut client).1),
+            value: Some(U256::zero()),
+            ..Default::default()
+        };
+        let tx_hash = client.call(eth_send_transaction(transaction)).unwrap();
+        wait_for_transaction(&mut client, tx_hash);
+        transactions.push(subscription.next_item().unwrap());
+        if transactions.len() >= 2 {
+            break assert!(true);
+        }
+    }
+}
+
 #[test]
 fn test_eth_subscribe_logs() {
     let mut client = ConnectorWrapper::new_from_env();
@@ -0,0 +1,185 @@
+use ethane::rpc;
+use ethane::tokens::{Erc1155, Erc20, Erc721, TokenEvent};
+use ethane::types::{Bytes, Log, TransactionRequest, H160, U256};
+use std::path::Path;
+
+use test_helper::*;
+
+fn send(client: &mut ConnectorWrapper, transaction: TransactionRequest) -> Vec<Log> {
+    let tx_hash = client.call(rpc::eth_send_transaction(transaction)).unwrap();
+    wait_for_transaction(client, tx_hash);
+    let receipt = client
+        .call(rpc::eth_get_transaction_receipt(tx_hash))
+        .unwrap()
+        .unwrap();
+    receipt.logs
+}
+
+#[test]
+fn test_tokens_erc20() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let address = create_account(&mut client).1;
+    let recipient = H160::from_low_u64_be(0x1234);
+    let path = Path::new(TEST_TOKENS_PATH);
+    let (token_address, _) = deploy_contract(&mut client, address, path, TEST_ERC20_NAME);
+    let token = Erc20::new(token_address);
+    let (multicall_address, _) = deploy_contract(
+        &mut client,
+        address,
+        Path::new(TEST_MULTICALL_PATH),
+        TEST_MULTICALL_NAME,
+    );
+
+    assert_eq!(
+        with_connector!(client, |connector| token.decimals(connector, None)).unwrap(),
+        18
+    );
+    assert_eq!(
+        with_connector!(client, |connector| token.symbol(connector, None)).unwrap(),
+        "TST"
+    );
+
+    let logs = send(
+        &mut client,
+        token.transfer(address, recipient, U256::from(300)),
+    );
+    assert_eq!(
+        TokenEvent::from_log(&logs[0]).unwrap(),
+        TokenEvent::Transfer {
+            from: address,
+            to: recipient,
+            value: U256::from(300),
+        }
+    );
+    assert_eq!(
+        with_connector!(client, |connector| token
+            .balance_of(connector, recipient, None))
+        .unwrap(),
+        U256::from(300)
+    );
+    assert_eq!(
+        with_connector!(client, |connector| token.balances_of(
+            connector,
+            &[address, recipient],
+            Some(multicall_address),
+            None
+        ))
+        .unwrap(),
+        vec![U256::from(700), U256::from(300)]
+    );
+
+    send(
+        &mut client,
+        token.approve(address, recipient, U256::from(50)),
+    );
+    assert_eq!(
+        with_connector!(client, |connector| token
+            .allowance(connector, address, recipient, None))
+        .unwrap(),
+        U256::from(50)
+    );
+}
+
+#[test]
+fn test_tokens_erc721() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let address = create_account(&mut client).1;
+    let recipient = H160::from_low_u64_be(0x1234);
+    let path = Path::new(TEST_TOKENS_PATH);
+    let (token_address, _) = deploy_contract(&mut client, address, path, TEST_ERC721_NAME);
+    let token = Erc721::new(token_address);
+
+    assert_eq!(
+        with_connector!(client, |connector| token.symbol(connector, None)).unwrap(),
+        "NFT"
+    );
+    assert_eq!(
+        with_connector!(client, |connector| token.owner_of(
+            connector,
+            U256::one(),
+            None
+        ))
+        .unwrap(),
+        address
+    );
+
+    let logs = send(
+        &mut client,
+        token.safe_transfer_from(address, recipient, U256::one()),
+    );
+    assert_eq!(
+        TokenEvent::from_log(&logs[0]).unwrap(),
+        TokenEvent::NftTransfer {
+            from: address,
+            to: recipient,
+            token_id: U256::one(),
+        }
+    );
+    assert_eq!(
+        with_connector!(client, |connector| token.owner_of(
+            connector,
+            U256::one(),
+            None
+        ))
+        .unwrap(),
+        recipient
+    );
+    assert_eq!(
+        with_connector!(client, |connector| token
+            .balance_of(connector, address, None))
+        .unwrap(),
+        U256::zero()
+    );
+}
+
+#[test]
+fn test_tokens_erc1155() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let address = create_account(&mut client).1;
+    let recipient = H160::from_low_u64_be(0x1234);
+    let path = Path::new(TEST_TOKENS_PATH);
+    let (token_address, _) = deploy_contract(&mut client, address, path, TEST_ERC1155_NAME);
+    let token = Erc1155::new(token_address);
+
+    assert_eq!(
+        with_connector!(client, |connector| token.balance_of(
+            connector,
+            address,
+            U256::one(),
+            None
+        ))
+        .unwrap(),
+        U256::from(10)
+    );
+
+    let logs = send(
+        &mut client,
+        token.safe_transfer_from(
+            address,
+            recipient,
+            U256::one(),
+            U256::from(4),
+            Bytes::default(),
+        ),
+    );
+    assert_eq!(
+        TokenEvent::from_log(&logs[0]).unwrap(),
+        TokenEvent::TransferSingle {
+            operator: address,
+            from: address,
+            to: recipient,
+            id: U256::one(),
+            value: U256::from(4),
+        }
+    );
+    assert_eq!(
+        with_connector!(client, |connector| token.balance_of_batch(
+            connector,
+            &[address, recipient],
+            &[U256::one(), U256::one()],
+            None
+        ))
+        .unwrap(),
+        vec![U256::from(6), U256::from(4)]
+    );
+}
@@ -0,0 +1,41 @@
+use ethane::rpc;
+use ethane::types::{BlockParameter, TraceFilter, TransactionRequest, U256};
+
+use test_helper::*;
+
+// The trace module is not provided by geth, run these against Erigon or Nethermind
+#[test]
+#[ignore]
+fn test_trace_block() {
+    let mut client = ConnectorWrapper::new_from_env();
+    rpc_call_test_some(&mut client, rpc::trace_block(None));
+}
+
+#[test]
+#[ignore]
+fn test_trace_transaction() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let transaction = TransactionRequest {
+        from: create_account(&mut client).1,
+        to: Some(create_account(&mut client).1),
+        value: Some(U256::zero()),
+        ..Default::default()
+    };
+    let tx_hash = client.call(rpc::eth_send_transaction(transaction)).unwrap();
+    wait_for_transaction(&mut client, tx_hash);
+    let traces = client.call(rpc::trace_transaction(tx_hash)).unwrap();
+    assert!(!traces.is_empty());
+}
+
+#[test]
+#[ignore]
+fn test_trace_filter() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let filter = TraceFilter {
+        from_block: Some(BlockParameter::Earliest),
+        to_block: Some(BlockParameter::Latest),
+        count: Some(10),
+        ..Default::default()
+    };
+    rpc_call_test_some(&mut client, rpc::trace_filter(filter));
+}
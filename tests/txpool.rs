@@ -1,5 +1,6 @@
 use ethane::rpc;
-use ethane::types::{TransactionRequest, U256};
+use ethane::types::{TransactionRequest, TxPoolSummary, U256};
+use std::str::FromStr;
 
 use test_helper::*;
 
@@ -26,6 +27,24 @@ fn test_txpool_content() {
     rpc_call_test_some(&mut client, rpc::txpool_content());
 }
 
+#[test]
+fn test_txpool_content_from() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let sender = create_account(&mut client).1;
+    let transaction = TransactionRequest {
+        from: sender,
+        to: Some(create_account(&mut client).1),
+        value: Some(U256::zero()),
+        ..Default::default()
+    };
+    for _ in 0..10 {
+        let _tx_hash = client
+            .call(rpc::eth_send_transaction(transaction.clone()))
+            .unwrap();
+    }
+    rpc_call_test_some(&mut client, rpc::txpool_content_from(sender));
+}
+
 #[test]
 fn test_txpool_inspect() {
     let mut client = ConnectorWrapper::new_from_env();
@@ -42,3 +61,28 @@ fn test_txpool_inspect() {
     }
     rpc_call_test_some(&mut client, rpc::txpool_inspect());
 }
+
+#[test]
+fn test_txpool_inspect_summaries() {
+    let mut client = ConnectorWrapper::new_from_env();
+    let transaction = TransactionRequest {
+        from: create_account(&mut client).1,
+        to: Some(create_account(&mut client).1),
+        value: Some(U256::zero()),
+        ..Default::default()
+    };
+    for _ in 0..10 {
+        let _tx_hash = client
+            .call(rpc::eth_send_transaction(transaction.clone()))
+            .unwrap();
+    }
+    let inspect = client.call(rpc::txpool_inspect()).unwrap();
+    for summary in inspect
+        .pending
+        .values()
+        .chain(inspect.queued.values())
+        .flat_map(|summaries| summaries.values())
+    {
+        assert!(TxPoolSummary::from_str(summary).is_ok());
+    }
+}